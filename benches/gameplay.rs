@@ -0,0 +1,178 @@
+//! headless frame time benchmarks for the gameplay plugins
+//!
+//! `cargo bench --bench gameplay --features headless [-- <scenario>...]` prints one json
+//! object per scenario, pipe it to a file to compare between commits
+
+use std::time::{Duration, Instant};
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+
+use learn_bevy::{
+    assets::MyAssets,
+    astroids::{Astroid, Rock},
+    health::Health,
+    sentry::Sentry,
+    ship::Player,
+    stage::{IntoMovingBundle, Stage},
+    state::GameState,
+    zones::{WorldSeed, Zone},
+    GamePlugins,
+};
+
+/// fixed so every run generates the same universe
+const SEED: u64 = 1;
+const FRAME: Duration = Duration::from_nanos(16_666_667);
+/// frames run before measuring so startup and the first zones are out of the way
+const WARMUP: usize = 30;
+/// updates allowed for the asset manifest to load
+const LOADING_LIMIT: usize = 10_000;
+
+struct Scenario {
+    name: &'static str,
+    frames: usize,
+    setup: fn(&mut World),
+    /// runs before every measured frame
+    step: fn(&mut World, usize),
+}
+
+const SCENARIOS: &[Scenario] = &[
+    Scenario {
+        name: "fly_50_zones",
+        frames: 500,
+        setup: |_| (),
+        step: fly,
+    },
+    Scenario {
+        name: "split_1000_astroids",
+        frames: 120,
+        setup: split,
+        step: |_, _| (),
+    },
+    Scenario {
+        name: "10_sentries_firing",
+        frames: 300,
+        setup: sentries,
+        step: |_, _| (),
+    },
+];
+
+fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(GamePlugins)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME))
+        .insert_resource(WorldSeed(SEED));
+    app.finish();
+    app.cleanup();
+    app
+}
+
+/// move the player one zone every ten frames, kept alive whatever it hits
+fn fly(world: &mut World, frame: usize) {
+    let step = Zone::SIZE * 2.0 / 10.0;
+    let mut q = world.query_filtered::<(&mut Transform, &mut Health), With<Player>>();
+    for (mut transform, mut health) in q.iter_mut(world) {
+        transform.translation.x = frame as f32 * step;
+        health.life = i32::MAX / 2;
+    }
+}
+
+/// a thousand big rocks around the origin that all die on the next frame
+fn split(world: &mut World) {
+    let rock = Astroid {
+        bulk: 8,
+        kind: Rock::Stone,
+    };
+    world.resource_scope(|world, assets: Mut<MyAssets>| {
+        for i in 0..1000 {
+            let position = Vec2::new((i % 40) as f32, (i / 40) as f32) * 25.0 + 200.0;
+            let transform = Transform::from_translation(position.extend(0.0));
+            world
+                .spawn(rock.bundle(&assets, transform, Vec2::ZERO))
+                .insert(Health { life: 0 });
+        }
+    });
+}
+
+/// a ring of sentries around rocks too tough to run out of
+fn sentries(world: &mut World) {
+    let rock = Astroid {
+        bulk: 4,
+        kind: Rock::Metal,
+    };
+    let center = Vec2::new(300.0, 300.0);
+    world.resource_scope(|world, assets: Mut<MyAssets>| {
+        for i in 0..10 {
+            let angle = i as f32 / 10.0 * std::f32::consts::TAU;
+            let position = center + Vec2::from_angle(angle) * 60.0;
+            let transform = Transform::from_translation(position.extend(0.0));
+            let sentry = Sentry::default();
+            world.spawn((sentry.stage(&assets, transform), sentry.equipment()));
+        }
+        for i in 0..20 {
+            let position = center + Vec2::new((i % 5) as f32 - 2.0, (i / 5) as f32 - 2.0) * 12.0;
+            let transform = Transform::from_translation(position.extend(0.0));
+            world
+                .spawn(rock.bundle(&assets, transform, Vec2::ZERO))
+                .insert(Health { life: i32::MAX / 2 });
+        }
+    });
+}
+
+fn run(scenario: &Scenario) -> Vec<Duration> {
+    let mut app = headless_app();
+    for _ in 0..LOADING_LIMIT {
+        if *app.world().resource::<State<GameState>>() != GameState::Loading {
+            break;
+        }
+        app.update();
+    }
+    assert_eq!(
+        *app.world().resource::<State<GameState>>(),
+        GameState::Play,
+        "assets did not load"
+    );
+    for _ in 0..WARMUP {
+        app.update();
+    }
+    (scenario.setup)(app.world_mut());
+    (0..scenario.frames)
+        .map(|frame| {
+            (scenario.step)(app.world_mut(), frame);
+            let start = Instant::now();
+            app.update();
+            start.elapsed()
+        })
+        .collect()
+}
+
+fn report(name: &str, mut times: Vec<Duration>) -> String {
+    times.sort();
+    let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+    let percentile = |p: f64| ms(times[((times.len() - 1) as f64 * p).round() as usize]);
+    let total: Duration = times.iter().sum();
+    format!(
+        r#"{{"scenario":"{name}","frames":{},"mean_ms":{:.3},"median_ms":{:.3},"p95_ms":{:.3},"max_ms":{:.3}}}"#,
+        times.len(),
+        ms(total) / times.len() as f64,
+        percentile(0.5),
+        percentile(0.95),
+        ms(*times.last().unwrap()),
+    )
+}
+
+fn main() {
+    // cargo passes --bench, anything else picks scenarios by name
+    let filters: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with("--"))
+        .collect();
+    for scenario in SCENARIOS {
+        let picked = filters.is_empty() || filters.iter().any(|f| scenario.name.contains(f));
+        if !picked {
+            continue;
+        }
+        eprintln!("running {}", scenario.name);
+        let times = run(scenario);
+        println!("{}", report(scenario.name, times));
+    }
+}
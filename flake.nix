@@ -59,7 +59,7 @@
           inherit src buildInputs;
           version = "0.1.0";
 
-          cargoExtraArgs = "--target wasm32-unknown-unknown";
+          cargoExtraArgs = "--target wasm32-unknown-unknown --no-default-features --features web";
           doCheck = false;
         };
 
@@ -75,7 +75,7 @@
         wasmBuild = craneLib.buildPackage {
           inherit src buildInputs;
           cargoArtifacts = wasmArtifacts;
-          cargoExtraArgs = "--target wasm32-unknown-unknown";
+          cargoExtraArgs = "--target wasm32-unknown-unknown --no-default-features --features web";
           doCheck = false;
           postFixup = ''
             mkdir $out/bin/wasm
@@ -142,7 +142,7 @@
         LIBS = pkgs.lib.makeLibraryPath libs;
         run = pkgs.writeShellScriptBin "run" ''
           export LD_LIBRARY_PATH=${LIBS}
-          ${rust}/bin/cargo run $@
+          ${rust}/bin/cargo run --features dynamic_linking $@
         '';
       in
         with pkgs; {
@@ -1,37 +1,62 @@
-use bevy::{math::primitives::Circle, prelude::*};
+use std::{collections::BTreeMap, error::Error, marker::PhantomData};
 
-use crate::schedule::InitStages;
+use bevy::{
+    asset::{io::Reader, ron, AssetLoader, AsyncReadExt, LoadContext},
+    math::primitives::{Capsule2d, Circle},
+    prelude::*,
+    reflect::Struct,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::{schedule::InitStages, tuning::Tuning};
 // use bevy::sprite::{MaterialMesh2dBundle, Mesh2dHandle};
 
-#[derive(Resource, Default)]
+/// handles to everything the game draws and plays
+#[derive(Resource, Default, Reflect)]
 pub struct MyAssets {
     pub asteroid_material: Handle<ColorMaterial>,
     pub astriod2: Handle<Image>,
     pub astriod: Handle<Image>,
     pub astriod_metal: Handle<Image>,
     pub ball: Handle<Mesh>,
+    pub black_hole_material: Handle<ColorMaterial>,
+    /// capsule shared by plasma bolts and hooks
+    pub bolt: Handle<Mesh>,
+    /// music layers, left empty when the manifest has no track for the mood
+    pub calm_music: Handle<AudioSource>,
+    pub combat_music: Handle<AudioSource>,
     pub crack: Handle<AudioSource>,
+    pub danger_music: Handle<AudioSource>,
     pub doing: Handle<AudioSource>,
+    pub game_over_music: Handle<AudioSource>,
+    pub hook_material: Handle<ColorMaterial>,
     pub laser_sound: Handle<AudioSource>,
     pub missles: Handle<Scene>,
     pub muffled_laser: Handle<AudioSource>,
+    pub paused_music: Handle<AudioSource>,
+    pub planet: Handle<Mesh>,
+    pub planet_material: Handle<ColorMaterial>,
+    pub plasma_material: Handle<ColorMaterial>,
     pub pop: Handle<AudioSource>,
     pub ship: Handle<Image>,
     pub slap: Handle<AudioSource>,
     pub turret: Handle<Image>,
+    /// live copy of the tuning file
+    pub tuning: Tuning,
 }
 
 pub struct AssetPlug;
 impl Plugin for AssetPlug {
     fn build(&self, app: &mut App) {
         app.init_resource::<MyAssets>()
+            .init_asset::<Manifest>()
+            .register_asset_loader(RonLoader::<Manifest>::new(&["manifest.ron"]))
             .add_systems(Startup, setup.in_set(InitStages::LoadAssets));
     }
 }
 
 fn setup(
     mut assets: ResMut<MyAssets>,
-    asset_server: Res<AssetServer>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut colors: ResMut<Assets<ColorMaterial>>,
 ) {
@@ -41,21 +66,108 @@ fn setup(
     let laser_color = Color::srgb(0., 1., 0.);
     let laser_color_handle = colors.add(laser_color);
 
+    let bolt_shape = Capsule2d::new(0.5, 2.0);
+    let plasma_color = Color::srgb(7.5, 1.0, 7.5);
+    let hook_color = Color::srgb(0., 0., 10.0);
+    let planet_color = Color::srgb(0.5, 0.7, 0.9);
+    let black_hole_color = Color::srgb(0.02, 0.0, 0.05);
+
+    // files come from the manifest while loading, these are made in code
     *assets = MyAssets {
         asteroid_material: laser_color_handle,
-        astriod2: asset_server.load("ice_planet.png"),
-        astriod: asset_server.load("baren.png"),
-        // astriod: asset_server.load("Planet.glb#Scene0"),
-        astriod_metal: asset_server.load("lava_planet.png"),
         ball: astroid_mesh,
-        crack: asset_server.load("524610__clearwavsound__fruit-crack.wav"),
-        doing: asset_server.load("funny_boing_1_miksmusic.wav"),
-        laser_sound: asset_server.load("laser-104024.mp3"),
-        missles: asset_server.load("BulletsPickup.glb#Scene0"),
-        muffled_laser: asset_server.load("muffled_laser_blast_samsterbirdies.mp3"),
-        pop: asset_server.load("ball_tap2073.wav"),
-        ship: asset_server.load("scout.png"),
-        slap: asset_server.load("glass_slapp1_cjspellsfish.wav"),
-        turret: asset_server.load("techno_turret2.png"),
+        black_hole_material: colors.add(black_hole_color),
+        bolt: meshes.add(bolt_shape),
+        hook_material: colors.add(hook_color),
+        planet_material: colors.add(planet_color),
+        plasma_material: colors.add(plasma_color),
+        ..default()
+    }
+}
+
+/// files to load into [`MyAssets`], grouped by asset type and keyed by field name
+#[derive(Asset, TypePath, Deserialize, Debug, Default)]
+pub struct Manifest {
+    #[serde(default)]
+    images: BTreeMap<String, String>,
+    #[serde(default)]
+    sounds: BTreeMap<String, String>,
+    #[serde(default)]
+    scenes: BTreeMap<String, String>,
+    #[serde(default)]
+    meshes: BTreeMap<String, String>,
+}
+
+impl MyAssets {
+    fn load_field<T: Asset>(
+        &mut self,
+        server: &AssetServer,
+        name: &str,
+        path: &str,
+    ) -> Result<UntypedHandle, String> {
+        let field = self
+            .field_mut(name)
+            .and_then(|field| field.downcast_mut::<Handle<T>>())
+            .ok_or_else(|| format!("{name}: no {} field by that name", T::short_type_path()))?;
+        *field = server.load(path.to_owned());
+        Ok(field.clone().untyped())
+    }
+
+    /// start loading every file in the manifest, entries naming no field are errors
+    pub fn load_manifest(
+        &mut self,
+        manifest: &Manifest,
+        server: &AssetServer,
+    ) -> Vec<Result<UntypedHandle, String>> {
+        let mut loads = Vec::new();
+        for (name, path) in manifest.images.iter() {
+            loads.push(self.load_field::<Image>(server, name, path));
+        }
+        for (name, path) in manifest.sounds.iter() {
+            loads.push(self.load_field::<AudioSource>(server, name, path));
+        }
+        for (name, path) in manifest.scenes.iter() {
+            loads.push(self.load_field::<Scene>(server, name, path));
+        }
+        for (name, path) in manifest.meshes.iter() {
+            loads.push(self.load_field::<Mesh>(server, name, path));
+        }
+        loads
+    }
+}
+
+/// reads a T from a ron file with one of the given extensions
+pub struct RonLoader<T> {
+    extensions: &'static [&'static str],
+    phantom: PhantomData<T>,
+}
+
+impl<T> RonLoader<T> {
+    pub fn new(extensions: &'static [&'static str]) -> Self {
+        Self {
+            extensions,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: Asset + DeserializeOwned> AssetLoader for RonLoader<T> {
+    type Asset = T;
+    type Settings = ();
+    type Error = Box<dyn Error + Send + Sync>;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<T, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.extensions
     }
 }
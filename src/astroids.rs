@@ -6,7 +6,12 @@ use rand_distr::Distribution;
 use rand_distr::Standard;
 
 use crate::assets::MyAssets;
+use crate::camera::CameraImpulse;
 use crate::collide_dmg::CollisionDamage;
+use crate::console::{ahead_of_player, arg, ConsoleApp};
+use crate::feedback::blast_dead;
+use crate::feedback::Blast;
+use crate::feedback::DeathBlast;
 use crate::health::cry_dead;
 use crate::health::Death;
 use crate::health::DeathCry;
@@ -14,10 +19,17 @@ use crate::health::DeathCry;
 // use crate::collide::CollisionDamage;
 // use crate::collide::HomeMadeCollider;
 use crate::health::Health;
+use crate::particles::ParticleConfig;
+use crate::persist::{Persist, PersistApp, Restage};
+use crate::pool::Pool;
+use crate::radar::{scan, Blip, RadarBlip, RadarSet};
 use crate::schedule::InGameSet;
+use crate::sound::Sfx;
+use crate::spatial::Indexed;
 use crate::stage::Extra;
 use crate::stage::IntoMovingBundle;
 use crate::stage::Stage;
+use crate::tuning::Tuning;
 
 pub struct AstriodPlug;
 
@@ -25,7 +37,20 @@ impl Plugin for AstriodPlug {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (split_dead, cry_dead::<Astroid>).in_set(InGameSet::Spawn),
+            (
+                split_dead,
+                cry_dead::<Astroid>,
+                blast_dead::<Astroid>,
+                shake_dead,
+            )
+                .in_set(InGameSet::Spawn),
+        )
+        .add_systems(Update, scan::<Astroid>.in_set(RadarSet::Scan))
+        .restage::<Astroid>()
+        .console_command(
+            "spawn astroid",
+            "<bulk> <stone|ice|metal> put a rock ahead of the ship",
+            spawn_astroid,
         );
         // .add_systems(Update, despawn_astroid.in_set(InGameSet::Despawn))
         // .add_systems(
@@ -44,6 +69,19 @@ pub enum Rock {
     Metal,
 }
 
+impl std::str::FromStr for Rock {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "stone" => Ok(Rock::Stone),
+            "ice" => Ok(Rock::Ice),
+            "metal" => Ok(Rock::Metal),
+            _ => Err(()),
+        }
+    }
+}
+
 impl Distribution<Rock> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Rock {
         // let stone_prob = 800;
@@ -60,6 +98,7 @@ impl Distribution<Rock> for Standard {
 }
 
 #[derive(Component, Debug, PartialEq, Eq, Hash, Clone, Copy, Reflect)]
+#[reflect(Component)]
 pub struct Astroid {
     pub bulk: u8,
     pub kind: Rock,
@@ -67,24 +106,19 @@ pub struct Astroid {
 
 impl Astroid {
     // const ROTATION_SPEED: f32 = 1.0;
-    const RADIUS_MOD: f32 = 2.5;
-    /// hit point scaling with size
-    const LIFE_MOD: i32 = 10;
-    /// hit point scaling with size
 
+    /// shards come and go in bursts, so they are drawn from the pool
     fn spawn(
         &self,
         assets: &Res<MyAssets>,
         particles: impl Iterator<Item = (Vec2, Vec2)>,
         cmds: &mut Commands,
+        pool: &mut Pool<Astroid>,
     ) {
-        let batch: Box<[_]> = particles
-            .map(|particle| {
-                let transform = Transform::from_translation(particle.0.extend(0.0));
-                self.bundle(assets, transform, particle.1)
-            })
-            .collect();
-        cmds.spawn_batch(batch);
+        for (position, velocity) in particles {
+            let transform = Transform::from_translation(position.extend(0.0));
+            pool.spawn(cmds, self.bundle(assets, transform, velocity));
+        }
     }
 
     fn damage(&self) -> CollisionDamage {
@@ -92,9 +126,9 @@ impl Astroid {
         CollisionDamage(1)
     }
 
-    fn health(&self) -> Health {
+    fn health(&self, tuning: &Tuning) -> Health {
         Health {
-            life: self.bulk as i32 * Self::LIFE_MOD,
+            life: self.bulk as i32 * tuning.astroid_life,
             ..Default::default()
         }
     }
@@ -103,22 +137,22 @@ impl Astroid {
         Collider::circle(1.0)
     }
 
-    fn radius(&self) -> f32 {
-        (self.bulk as f32).sqrt() * Self::RADIUS_MOD
+    pub fn radius(&self, tuning: &Tuning) -> f32 {
+        (self.bulk as f32).sqrt() * tuning.astroid_radius
     }
 
-    const SPEED_MOD: f32 = 5.0;
-    pub fn random_velocity() -> Vec2 {
-        let mut rng = rand::thread_rng();
-
-        let v_unit = random_unit_vec(&mut rng);
-        let factor: f32 = rng.gen_range(0.0..Self::SPEED_MOD);
+    /// from this size and up deaths are felt through the camera
+    const BIG_BULK: u8 = 25;
+    /// drift in a random direction, spread scales the top speed
+    pub fn random_velocity(rng: &mut impl Rng, tuning: &Tuning, spread: f32) -> Vec2 {
+        let v_unit = random_unit_vec(rng);
+        let factor: f32 = rng.gen_range(0.0..=tuning.astroid_speed * spread);
         v_unit * factor
         // Velocity::default()
     }
 
-    fn scale(&self) -> Vec3 {
-        Vec3::splat(self.radius())
+    fn scale(&self, tuning: &Tuning) -> Vec3 {
+        Vec3::splat(self.radius(tuning))
     }
 }
 
@@ -133,6 +167,7 @@ fn split_dead(
     mut death_events: EventReader<Death>,
     q: Query<(&Transform, &LinearVelocity, &Astroid)>,
     assets: Res<MyAssets>,
+    mut pool: Pool<Astroid>,
 ) {
     for death in death_events.read() {
         let Ok((&transform, &velocity, &astroid)) = q.get(**death) else {
@@ -150,21 +185,77 @@ fn split_dead(
             let origin = transform.translation.truncate();
             let c = 0.5;
 
-            let offset = (v - *velocity).normalize() * (shard.radius() * 1.1 + c);
+            let offset = (v - *velocity).normalize() * (shard.radius(&assets.tuning) * 1.1 + c);
             let spawn_coord = origin + offset;
             (spawn_coord, v)
         });
-        shard.spawn(&assets, particles, &mut cmds);
+        shard.spawn(&assets, particles, &mut cmds, &mut pool);
+    }
+}
+
+fn spawn_astroid(world: &mut World, args: &[&str]) -> Result<String, String> {
+    let bulk: u8 = arg(args, 0, "bulk")?;
+    let kind: Rock = arg(args, 1, "kind")?;
+    if bulk == 0 {
+        return Err("<bulk> must be at least 1".into());
+    }
+    let astroid = Astroid { bulk, kind };
+    let tuning = world.resource::<MyAssets>().tuning;
+    let transform = ahead_of_player(world, 20.0 + astroid.radius(&tuning))?;
+    world.resource_scope(|world, assets: Mut<MyAssets>| {
+        world.spawn(astroid.bundle(&assets, transform, Vec2::ZERO));
+    });
+    Ok(format!("spawned {astroid:?}"))
+}
+
+/// big rocks breaking up shake the camera
+fn shake_dead(
+    mut death_events: EventReader<Death>,
+    mut writer: EventWriter<CameraImpulse>,
+    q: Query<(&Transform, &Astroid)>,
+) {
+    for death in death_events.read() {
+        let Ok((transform, astroid)) = q.get(**death) else {
+            continue;
+        };
+        if astroid.bulk < Astroid::BIG_BULK {
+            continue;
+        }
+        writer.send(CameraImpulse {
+            trauma: astroid.bulk as f32 / 200.0,
+            origin: Some(transform.translation.truncate()),
+        });
     }
 }
 
 impl DeathCry for Astroid {
-    fn cry(&self, assets: &MyAssets) -> AudioBundle {
-        let sound = AudioBundle {
-            source: assets.crack.clone(),
-            settings: PlaybackSettings::DESPAWN,
+    fn cry(&self) -> Sfx {
+        Sfx::Crack
+    }
+}
+
+impl DeathBlast for Astroid {
+    fn blast(&self) -> Blast {
+        Blast {
+            particles: ParticleConfig::rock_dust(self.kind),
+            count: 6 + self.bulk as usize / 2,
+            fireball: None,
+        }
+    }
+}
+
+impl RadarBlip for Astroid {
+    fn blip(&self) -> Blip {
+        let color = match self.kind {
+            Rock::Stone => Color::srgb(0.6, 0.55, 0.5),
+            Rock::Ice => Color::srgb(0.5, 0.8, 1.0),
+            Rock::Metal => Color::srgb(1.0, 0.5, 0.2),
         };
-        sound
+        // sized by bulk alone so the radar does not follow tuning
+        Blip {
+            color,
+            size: 2.0 + (self.bulk as f32).sqrt() * 1.25,
+        }
     }
 }
 
@@ -191,26 +282,42 @@ fn explode_veclocity(origin_velocity: Vec2, n: usize) -> Vec<Vec2> {
 }
 
 impl Extra for Astroid {
-    type Extras = (Health, Collider, CollisionDamage, Name, RigidBody);
+    type Extras = (
+        Health,
+        Collider,
+        CollisionDamage,
+        Name,
+        RigidBody,
+        Persist,
+        Indexed,
+    );
 
-    fn extra(&self) -> Self::Extras {
+    fn extra(&self, assets: &MyAssets) -> Self::Extras {
         (
-            self.health(),
+            self.health(&assets.tuning),
             self.collider(),
             self.damage(),
             Name::new("Astroid"),
             RigidBody::Dynamic,
+            Persist,
+            Indexed,
         )
     }
 }
 
+impl Restage for Astroid {
+    fn restage(&self, entity: &mut EntityWorldMut, assets: &MyAssets) {
+        entity.insert(self.bundle(assets, Transform::default(), Vec2::ZERO));
+    }
+}
+
 impl Stage for Astroid {
     fn stage(
         self,
-        assets: &Res<MyAssets>, //
+        assets: &MyAssets, //
         transform: Transform,
     ) -> impl Bundle {
-        let transform = transform.with_scale(self.scale());
+        let transform = transform.with_scale(self.scale(&assets.tuning));
         // let mesh = Mesh2dHandle(assets.ball.clone());
 
         // let texture = assets.astriod.clone();
@@ -0,0 +1,210 @@
+use bevy::prelude::*;
+use rand::Rng;
+use rand_pcg::Pcg64;
+use rand_seeder::Seeder;
+
+use crate::{
+    astroids::Rock,
+    gravity::GravityWell,
+    zones::{WorldSeed, Zone},
+};
+
+/// zones across one noise cell, bigger means larger regions
+const REGION_SIZE: f32 = 6.0;
+
+/// the character of a region of space, decides what a zone is populated with
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum Biome {
+    #[default]
+    Plain,
+    IceField,
+    MetalBelt,
+    Void,
+    DebrisCloud,
+}
+
+/// generation knobs for the zones of one biome
+#[derive(Debug, Clone, Copy)]
+pub struct BiomeParams {
+    /// asteroids in a zone, picked uniformly in min..max
+    pub count: (u8, u8),
+    /// binomial trials and probability, bulk is (sample + 1)^2
+    pub bulk: (u64, f64),
+    /// relative odds of stone, ice and metal
+    pub rocks: [u32; 3],
+    /// multiple of the normal asteroid drift speed
+    pub speed: f32,
+    /// odds of a planet and of a black hole sitting in the middle of a zone
+    pub wells: [f64; 2],
+}
+
+impl Biome {
+    pub fn params(&self) -> BiomeParams {
+        match self {
+            Biome::Plain => BiomeParams {
+                count: (10, 100),
+                bulk: (15, 0.1),
+                rocks: [600, 300, 100],
+                speed: 1.0,
+                wells: [0.1, 0.01],
+            },
+            Biome::IceField => BiomeParams {
+                count: (40, 110),
+                bulk: (15, 0.15),
+                rocks: [150, 800, 50],
+                speed: 0.5,
+                wells: [0.12, 0.0],
+            },
+            Biome::MetalBelt => BiomeParams {
+                count: (50, 120),
+                bulk: (15, 0.12),
+                rocks: [450, 50, 500],
+                speed: 1.5,
+                wells: [0.15, 0.02],
+            },
+            Biome::Void => BiomeParams {
+                count: (0, 6),
+                bulk: (15, 0.25),
+                rocks: [700, 200, 100],
+                speed: 0.3,
+                wells: [0.05, 0.25],
+            },
+            Biome::DebrisCloud => BiomeParams {
+                count: (120, 200),
+                bulk: (8, 0.1),
+                rocks: [800, 100, 100],
+                speed: 2.5,
+                wells: [0.05, 0.02],
+            },
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            Biome::Plain => Color::srgb(0.25, 0.6, 0.25),
+            Biome::IceField => Color::srgb(0.3, 0.55, 0.8),
+            Biome::MetalBelt => Color::srgb(0.8, 0.45, 0.2),
+            Biome::Void => Color::srgb(0.15, 0.15, 0.25),
+            Biome::DebrisCloud => Color::srgb(0.55, 0.5, 0.45),
+        }
+    }
+
+    /// sampled from noise so neighboring zones tend to share a biome
+    pub fn at(zone: Zone, world: WorldSeed) -> Self {
+        let (x, y) = (zone.col as f32, zone.row as f32);
+        let density = noise(x, y, world, 0);
+        let richness = noise(x, y, world, 1);
+        if density < 0.25 {
+            Biome::Void
+        } else if density > 0.75 {
+            Biome::DebrisCloud
+        } else if richness < 0.3 {
+            Biome::IceField
+        } else if richness > 0.7 {
+            Biome::MetalBelt
+        } else {
+            Biome::Plain
+        }
+    }
+}
+
+impl BiomeParams {
+    /// zones further out are denser, with bigger rocks and more metal, danger is 0..1
+    pub fn scaled(mut self, danger: f32) -> Self {
+        let denser = 1.0 + danger;
+        // the range has to stay non empty
+        self.count = (
+            (self.count.0 as f32 * denser).min(254.0) as u8,
+            (self.count.1 as f32 * denser).min(255.0) as u8,
+        );
+        self.bulk.1 = (self.bulk.1 * (1.0 + 0.5 * danger as f64)).min(0.9);
+        self.rocks[2] = (self.rocks[2] as f32 * (1.0 + 3.0 * danger)) as u32;
+        self
+    }
+
+    pub fn rock(&self, rng: &mut impl Rng) -> Rock {
+        let [stone, ice, metal] = self.rocks;
+        let roll = rng.gen_range(0..stone + ice + metal);
+        if roll < stone {
+            Rock::Stone
+        } else if roll < stone + ice {
+            Rock::Ice
+        } else {
+            Rock::Metal
+        }
+    }
+
+    pub fn well(&self, rng: &mut impl Rng) -> Option<GravityWell> {
+        let [planet, black_hole] = self.wells;
+        let roll: f64 = rng.gen();
+        if roll < planet {
+            Some(GravityWell::Planet)
+        } else if roll < planet + black_hole {
+            Some(GravityWell::BlackHole)
+        } else {
+            None
+        }
+    }
+}
+
+/// random value in 0..1 fixed to a lattice point
+fn lattice(x: i32, y: i32, world: WorldSeed, channel: u8) -> f32 {
+    let mut rng: Pcg64 = Seeder::from((world, x, y, channel)).make_rng();
+    rng.gen()
+}
+
+/// smooth value noise in 0..1, neighboring zones get similar values
+fn noise(x: f32, y: f32, world: WorldSeed, channel: u8) -> f32 {
+    let (x, y) = (x / REGION_SIZE, y / REGION_SIZE);
+    let (x0, y0) = (x.floor(), y.floor());
+    let smooth = |t: f32| t * t * (3.0 - 2.0 * t);
+    let (tx, ty) = (smooth(x - x0), smooth(y - y0));
+    let (ix, iy) = (x0 as i32, y0 as i32);
+
+    let corner = |dx: i32, dy: i32| lattice(ix + dx, iy + dy, world, channel);
+    let bottom = corner(0, 0).lerp(corner(1, 0), tx);
+    let top = corner(0, 1).lerp(corner(1, 1), tx);
+    bottom.lerp(top, ty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BIOMES: [Biome; 5] = [
+        Biome::Plain,
+        Biome::IceField,
+        Biome::MetalBelt,
+        Biome::Void,
+        Biome::DebrisCloud,
+    ];
+
+    #[test]
+    fn no_danger_changes_nothing() {
+        for biome in BIOMES {
+            let params = biome.params();
+            let scaled = params.scaled(0.0);
+            assert_eq!(scaled.count, params.count);
+            assert_eq!(scaled.bulk, params.bulk);
+            assert_eq!(scaled.rocks, params.rocks);
+        }
+    }
+
+    #[test]
+    fn count_range_stays_non_empty() {
+        for biome in BIOMES {
+            for danger in [0.0, 0.25, 0.5, 0.9, 1.0] {
+                let (low, high) = biome.params().scaled(danger).count;
+                assert!(low < high, "{biome:?} at {danger}: {low}..{high}");
+            }
+        }
+    }
+
+    #[test]
+    fn crowded_biomes_hit_the_cap_without_emptying() {
+        let mut params = Biome::DebrisCloud.params();
+        params.count = (250, 255);
+        let (low, high) = params.scaled(1.0).count;
+        assert_eq!((low, high), (254, 255));
+    }
+}
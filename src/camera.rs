@@ -1,19 +1,49 @@
 use avian2d::prelude::*;
 use bevy::{
     core_pipeline::{bloom::BloomSettings, tonemapping::Tonemapping},
+    input::{
+        gestures::PinchGesture,
+        mouse::{MouseScrollUnit, MouseWheel},
+    },
     prelude::*,
 };
 
-use crate::{despawn::Keep, ship::SpaceShip};
+use crate::{
+    despawn::Keep,
+    launch::LaunchConfig,
+    schedule::{InGameSet, InitStages},
+    ship::SpaceShip,
+    sound,
+    zones::WorldSeed,
+};
 
-// const CAM_DISTANCE: f32 = 140.;
-// const CHASE_FACTOR: f32 = 1.0;
+/// zoom change per wheel notch
+const WHEEL_ZOOM: f32 = 0.1;
+/// impulses further away than this from the camera focus are not felt
+const SHAKE_RANGE: f32 = 200.0;
+/// share of the window width on each side taken by the touch buttons
+const TOUCH_MARGIN: f32 = 0.15;
+/// steps the shake down to half, off and back to full
+const SHAKE_KEY: KeyCode = KeyCode::KeyK;
 
 pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, spawn_camera);
+        app.register_type::<CameraRig>()
+            .register_type::<ScreenShake>()
+            .register_type::<ShakeSettings>()
+            .init_resource::<ShakeSettings>()
+            .add_event::<CameraImpulse>();
+        // after the launch seed is in, the start depends on it
+        app.add_systems(Startup, spawn_camera.in_set(InitStages::Spawn));
+        app.add_systems(Update, (zoom_ctrl, shake_ctrl).in_set(InGameSet::UI));
+        app.add_systems(
+            Update,
+            (shake_on_impact, add_trauma)
+                .chain()
+                .in_set(InGameSet::CollisionDetection),
+        );
         app.add_systems(
             PostUpdate,
             fallow_player
@@ -28,26 +58,235 @@ impl Plugin for CameraPlugin {
     }
 }
 
-fn fallow_player(
-    mut q: Query<&mut Transform, With<Camera>>,
-    q_player: Query<&Transform, (Without<Camera>, With<SpaceShip>)>,
-    _time: Res<Time>,
+/// how the camera chases the player ship
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct CameraRig {
+    /// how fast the camera closes in on its target, higher is snappier
+    pub damping: f32,
+    /// seconds of ship velocity the camera leads by
+    pub look_ahead: f32,
+    /// relative zoom out per unit of ship speed
+    pub speed_zoom: f32,
+    pub min_zoom: f32,
+    pub max_zoom: f32,
+    /// zoom picked by the player with wheel or pinch
+    pub zoom: f32,
+    /// point the camera is smoothly tracking
+    pub focus: Vec2,
+}
+
+impl Default for CameraRig {
+    fn default() -> Self {
+        Self {
+            damping: 4.0,
+            look_ahead: 0.6,
+            speed_zoom: 0.01,
+            min_zoom: 0.04,
+            max_zoom: 0.4,
+            zoom: 0.1,
+            focus: Vec2::ZERO,
+        }
+    }
+}
+
+/// accessibility setting, 0.0 turns screen shake off
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct ShakeSettings {
+    pub scale: f32,
+}
+
+impl Default for ShakeSettings {
+    fn default() -> Self {
+        Self { scale: 1.0 }
+    }
+}
+
+impl ShakeSettings {
+    /// nudge the scale, kept within 0..1
+    pub fn change(&mut self, delta: f32) {
+        self.scale = (self.scale + delta).clamp(0.0, 1.0);
+    }
+
+    /// full, half, off and around again
+    pub fn cycle(&mut self) {
+        self.scale = if self.scale <= 0.0 {
+            1.0
+        } else {
+            (self.scale - 0.5).max(0.0)
+        };
+    }
+}
+
+/// trauma based shake, the offset grows with trauma squared
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct ScreenShake {
+    /// 0.0 is calm and 1.0 is as violent as it gets
+    pub trauma: f32,
+    /// trauma lost per second
+    pub decay: f32,
+    pub max_offset: f32,
+    pub max_roll: f32,
+    pub frequency: f32,
+}
+
+impl Default for ScreenShake {
+    fn default() -> Self {
+        Self {
+            trauma: 0.0,
+            decay: 1.2,
+            max_offset: 3.0,
+            max_roll: 3.0_f32.to_radians(),
+            frequency: 15.0,
+        }
+    }
+}
+
+/// kick the camera, impulses with an origin fade out with distance to the camera
+#[derive(Event, Debug, Clone, Copy)]
+pub struct CameraImpulse {
+    pub trauma: f32,
+    pub origin: Option<Vec2>,
+}
+
+impl CameraRig {
+    fn zoom_by(&mut self, factor: f32) {
+        self.zoom = (self.zoom * factor).clamp(self.min_zoom, self.max_zoom);
+    }
+}
+
+fn shake_ctrl(keys: Res<ButtonInput<KeyCode>>, mut settings: ResMut<ShakeSettings>) {
+    if keys.just_pressed(SHAKE_KEY) {
+        settings.cycle();
+        info!("screen shake at {}%", settings.scale * 100.0);
+    }
+}
+
+fn zoom_ctrl(
+    mut q: Query<&mut CameraRig>,
+    mut wheel: EventReader<MouseWheel>,
+    mut pinch: EventReader<PinchGesture>,
+    touches: Res<Touches>,
+    window_q: Query<&Window>,
 ) {
-    let player = q_player.get_single();
-    let camera = q.get_single_mut();
+    let Ok(mut rig) = q.get_single_mut() else {
+        return;
+    };
+    for event in wheel.read() {
+        let notches = match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / 100.0,
+        };
+        rig.zoom_by(1.0 - notches * WHEEL_ZOOM);
+    }
+    for PinchGesture(delta) in pinch.read() {
+        rig.zoom_by(1.0 - delta);
+    }
 
-    if let (Ok(mut camera), Ok(player)) = (camera, player) {
-        // let Vec3 { x, y, .. } = player.translation;
-        // let z = camera.translation.z;
-        // let target = Vec3 { x, y, z };
-        // let dt = time.delta_seconds();
-        // camera.translation = camera.translation.lerp(target, dt * CHASE_FACTOR);
-        camera.translation[0] = player.translation[0];
-        camera.translation[1] = player.translation[1];
+    // two fingers on the open playfield, fingers on the touch buttons are steering
+    let Ok(window) = window_q.get_single() else {
+        return;
+    };
+    let margin = window.width() * TOUCH_MARGIN;
+    let on_field = |x: f32| x > margin && x < window.width() - margin;
+    let fingers: Vec<_> = touches
+        .iter()
+        .filter(|touch| on_field(touch.start_position().x))
+        .collect();
+    if let [a, b] = fingers[..] {
+        let before = a.previous_position().distance(b.previous_position());
+        let now = a.position().distance(b.position());
+        if before > 0.0 && now > 0.0 {
+            rig.zoom_by(before / now);
+        }
+    }
+}
+
+fn shake_on_impact(
+    mut reader: EventReader<CollisionStarted>,
+    mut writer: EventWriter<CameraImpulse>,
+    collisions: Res<Collisions>,
+    ship_q: Query<(), With<SpaceShip>>,
+) {
+    for CollisionStarted(ent1, ent2) in reader.read() {
+        if !ship_q.contains(*ent1) && !ship_q.contains(*ent2) {
+            continue;
+        }
+        let Some(contacts) = collisions.get(*ent1, *ent2) else {
+            continue;
+        };
+        writer.send(CameraImpulse {
+            trauma: (contacts.total_normal_impulse * 0.002).min(0.6),
+            origin: None,
+        });
+    }
+}
+
+fn add_trauma(
+    mut reader: EventReader<CameraImpulse>,
+    mut q: Query<(&mut ScreenShake, &CameraRig)>,
+) {
+    let Ok((mut shake, rig)) = q.get_single_mut() else {
+        return;
+    };
+    for impulse in reader.read() {
+        let falloff = match impulse.origin {
+            Some(origin) => 1.0 - (origin.distance(rig.focus) / SHAKE_RANGE).min(1.0),
+            None => 1.0,
+        };
+        shake.trauma = (shake.trauma + impulse.trauma * falloff).min(1.0);
     }
 }
 
-fn spawn_camera(mut commands: Commands) {
+/// smooth noise in -1..1
+fn wobble(t: f32, seed: f32) -> f32 {
+    ((t + seed).sin() + (t * 2.3 + seed * 1.7).sin() * 0.5) / 1.5
+}
+
+fn fallow_player(
+    mut q: Query<(
+        &mut Transform,
+        &mut OrthographicProjection,
+        &mut CameraRig,
+        &mut ScreenShake,
+    )>,
+    q_player: Query<(&Transform, &LinearVelocity), (Without<CameraRig>, With<SpaceShip>)>,
+    settings: Res<ShakeSettings>,
+    time: Res<Time>,
+) {
+    let Ok((mut camera, mut projection, mut rig, mut shake)) = q.get_single_mut() else {
+        return;
+    };
+    let Ok((player, velocity)) = q_player.get_single() else {
+        return;
+    };
+
+    let dt = time.delta_seconds();
+    let blend = 1.0 - (-rig.damping * dt).exp();
+
+    let target = player.translation.truncate() + velocity.0 * rig.look_ahead;
+    rig.focus = rig.focus.lerp(target, blend);
+
+    let speed_zoom = 1.0 + velocity.length() * rig.speed_zoom;
+    let scale = (rig.zoom * speed_zoom).clamp(rig.min_zoom, rig.max_zoom);
+    projection.scale = projection.scale.lerp(scale, blend);
+
+    shake.trauma = (shake.trauma - shake.decay * dt).max(0.0);
+    let strength = shake.trauma.powi(2) * settings.scale;
+    let t = time.elapsed_seconds() * shake.frequency;
+    // offsets are tuned at the default zoom and scale with it
+    let reach = shake.max_offset * projection.scale / CameraRig::default().zoom;
+    let offset = Vec2::new(wobble(t, 0.0), wobble(t, 10.0)) * reach * strength;
+    let roll = wobble(t, 20.0) * shake.max_roll * strength;
+
+    camera.translation.x = rig.focus.x + offset.x;
+    camera.translation.y = rig.focus.y + offset.y;
+    camera.rotation = Quat::from_rotation_z(roll);
+}
+
+fn spawn_camera(mut commands: Commands, launch: Res<LaunchConfig>, world: Res<WorldSeed>) {
     let mut camera = Camera2dBundle {
         camera: Camera {
             hdr: true,
@@ -57,6 +296,18 @@ fn spawn_camera(mut commands: Commands) {
         ..Default::default()
     };
     let bloom = BloomSettings::default();
-    camera.projection.scale = 0.1;
-    commands.spawn((camera, Keep, bloom));
+    let rig = CameraRig {
+        focus: launch.start(*world),
+        ..default()
+    };
+    camera.projection.scale = rig.zoom;
+    camera.transform.translation = rig.focus.extend(camera.transform.translation.z);
+    commands.spawn((
+        camera,
+        Keep,
+        bloom,
+        rig,
+        ScreenShake::default(),
+        sound::listener(),
+    ));
 }
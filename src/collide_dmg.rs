@@ -1,13 +1,19 @@
 use avian2d::prelude::*;
 use bevy::{ecs::query::QueryFilter, prelude::*};
 
-use crate::{guns::Plasma, health::Health};
+use crate::{
+    difficulty::Difficulty,
+    guns::Plasma,
+    health::{Health, Invulnerable},
+    ship::Player,
+};
 
 pub struct CollideDamagePlugin;
 
 impl Plugin for CollideDamagePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, contact_damage::<(), ()>);
+        app.add_event::<DamageEvent>();
+        app.add_systems(Update, contact_damage::<(), Without<Invulnerable>>);
         // app.add_systems(Update, contact_damage::<With<Plasma>, Without<Plasma>>);
         // app.add_systems(Update, contact_damage::<Without<Plasma>, ()>);
     }
@@ -16,10 +22,20 @@ impl Plugin for CollideDamagePlugin {
 #[derive(Component, Deref, DerefMut)]
 pub struct CollisionDamage(pub i32);
 
+/// sent for every hit that takes health from an entity
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DamageEvent {
+    pub entity: Entity,
+    pub amount: i32,
+}
+
 fn contact_damage<DmgFilter, HealthFilter>(
     collision_event_reader: EventReader<CollisionStarted>,
     health_q: Query<&mut Health, HealthFilter>,
     damage_q: Query<&CollisionDamage, DmgFilter>,
+    player_q: Query<(), With<Player>>,
+    difficulty: Res<Difficulty>,
+    mut writer: EventWriter<DamageEvent>,
 ) where
     DmgFilter: QueryFilter,
     HealthFilter: QueryFilter,
@@ -27,14 +43,32 @@ fn contact_damage<DmgFilter, HealthFilter>(
     {
         let mut collision_event_reader = collision_event_reader;
         let mut health_q = health_q;
+        // the player feels the difficulty, everything else takes damage as is
+        let amount = |dmg: &CollisionDamage, target: Entity| {
+            if player_q.contains(target) {
+                difficulty.damage_taken(**dmg)
+            } else {
+                **dmg
+            }
+        };
         for CollisionStarted(ent1, ent2) in collision_event_reader.read() {
             let ent1 = *ent1;
             let ent2 = *ent2;
             if let (Ok(dmg), Ok(mut health)) = (damage_q.get(ent1), health_q.get_mut(ent2)) {
-                **health -= **dmg
+                let amount = amount(dmg, ent2);
+                **health -= amount;
+                writer.send(DamageEvent {
+                    entity: ent2,
+                    amount,
+                });
             }
             if let (Ok(dmg), Ok(mut health)) = (damage_q.get(ent2), health_q.get_mut(ent1)) {
-                **health -= **dmg
+                let amount = amount(dmg, ent1);
+                **health -= amount;
+                writer.send(DamageEvent {
+                    entity: ent1,
+                    amount,
+                });
             }
         }
     };
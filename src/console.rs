@@ -0,0 +1,373 @@
+//! drop down developer console, opened with the key left of 1
+//!
+//! plugins add their own commands with [`ConsoleApp::console_command`]
+
+use std::{collections::BTreeMap, str::FromStr};
+
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState, InputSystem,
+    },
+    prelude::*,
+};
+
+use crate::ship::Player;
+
+const TOGGLE: KeyCode = KeyCode::Backquote;
+/// lines kept in the scrollback
+const LOG_LINES: usize = 100;
+/// lines of the scrollback shown
+const SHOWN_LINES: usize = 14;
+const TEXT_COLOR: Color = Color::srgb(0.85, 0.95, 0.85);
+const ERROR_COLOR: Color = Color::srgb(1.0, 0.4, 0.4);
+
+pub struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Console>()
+            .add_systems(Startup, spawn_console)
+            .add_systems(PreUpdate, console_input.after(InputSystem))
+            .add_systems(Update, (run_commands, show_console).chain())
+            .console_command("help", "list all commands", help)
+            .console_command("timescale", "[<speed>] show or set game speed", timescale);
+    }
+}
+
+/// what a command does with its arguments, the message is printed to the console
+pub type ConsoleFn = fn(&mut World, &[&str]) -> Result<String, String>;
+
+struct ConsoleCommand {
+    help: &'static str,
+    run: ConsoleFn,
+}
+
+/// every command, keyed by the words that call it
+#[derive(Resource, Default)]
+pub struct ConsoleCommands(BTreeMap<&'static str, ConsoleCommand>);
+
+impl ConsoleCommands {
+    /// the command with the longest name matching the start of the line, and where its arguments begin
+    fn find(&self, words: &[&str]) -> Option<(ConsoleFn, usize)> {
+        self.0
+            .iter()
+            .filter_map(|(name, command)| {
+                let name: Vec<&str> = name.split(' ').collect();
+                words
+                    .starts_with(&name)
+                    .then_some((command.run, name.len()))
+            })
+            .max_by_key(|(_, len)| *len)
+    }
+}
+
+pub trait ConsoleApp {
+    /// call `run` when a line starts with `name`, which may be several words like `spawn sentry`
+    fn console_command(
+        &mut self,
+        name: &'static str,
+        help: &'static str,
+        run: ConsoleFn,
+    ) -> &mut Self;
+}
+
+impl ConsoleApp for App {
+    fn console_command(
+        &mut self,
+        name: &'static str,
+        help: &'static str,
+        run: ConsoleFn,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_insert_with(ConsoleCommands::default)
+            .0
+            .insert(name, ConsoleCommand { help, run });
+        self
+    }
+}
+
+/// argument `i` parsed, or a message saying what was expected
+pub fn arg<T: FromStr>(args: &[&str], i: usize, name: &str) -> Result<T, String> {
+    let raw = args.get(i).ok_or(format!("missing <{name}>"))?;
+    raw.parse()
+        .map_err(|_| format!("<{name}> can not be '{raw}'"))
+}
+
+/// the ship, for commands that act on the player
+pub fn player(world: &mut World) -> Result<Entity, String> {
+    world
+        .query_filtered::<Entity, With<Player>>()
+        .get_single(world)
+        .map_err(|_| "no player ship".to_string())
+}
+
+/// a spot just ahead of the ship to put things at
+pub fn ahead_of_player(world: &mut World, distance: f32) -> Result<Transform, String> {
+    let ship = player(world)?;
+    let transform = world
+        .get::<Transform>(ship)
+        .ok_or("ship has no transform")?;
+    // the ship model is flipped, it flies along its down axis
+    let position = transform.translation - transform.up() * distance;
+    Ok(Transform::from_translation(position.with_z(0.0)))
+}
+
+#[derive(Resource, Default)]
+struct Console {
+    open: bool,
+    input: String,
+    log: Vec<(String, bool)>,
+    /// lines entered since commands last ran
+    submitted: Vec<String>,
+}
+
+impl Console {
+    fn print(&mut self, line: String, error: bool) {
+        self.log.push((line, error));
+        let extra = self.log.len().saturating_sub(LOG_LINES);
+        self.log.drain(..extra);
+    }
+}
+
+#[derive(Component)]
+struct ConsoleRoot;
+
+#[derive(Component)]
+struct ConsoleLog;
+
+#[derive(Component)]
+struct ConsoleInput;
+
+/// typing goes to the console while it is open, the game does not see the keys
+fn console_input(
+    mut console: ResMut<Console>,
+    mut reader: EventReader<KeyboardInput>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+) {
+    // keys closing the console are not passed on either
+    let was_open = console.open;
+    for event in reader.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        if event.key_code == TOGGLE {
+            console.open = !console.open;
+            continue;
+        }
+        if !console.open {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Character(text) => console.input.push_str(text),
+            Key::Space => console.input.push(' '),
+            Key::Backspace => {
+                console.input.pop();
+            }
+            Key::Enter => {
+                let line = std::mem::take(&mut console.input);
+                if !line.trim().is_empty() {
+                    console.submitted.push(line);
+                }
+            }
+            Key::Escape => console.open = false,
+            _ => (),
+        }
+    }
+    if was_open || console.open {
+        keys.reset_all();
+    }
+}
+
+fn run_commands(world: &mut World) {
+    let lines = std::mem::take(&mut world.resource_mut::<Console>().submitted);
+    for line in lines {
+        world
+            .resource_mut::<Console>()
+            .print(format!("> {line}"), false);
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let found = world.resource::<ConsoleCommands>().find(&words);
+        let result = match found {
+            Some((run, skip)) => run(world, &words[skip..]),
+            None => Err(format!("unknown command '{}', try help", words[0])),
+        };
+        let mut console = world.resource_mut::<Console>();
+        match result {
+            Ok(message) if message.is_empty() => (),
+            Ok(message) => console.print(message, false),
+            Err(message) => {
+                warn!("console: {line}: {message}");
+                console.print(message, true);
+            }
+        }
+    }
+}
+
+fn help(world: &mut World, _: &[&str]) -> Result<String, String> {
+    let commands = world.resource::<ConsoleCommands>();
+    let lines: Vec<String> = commands
+        .0
+        .iter()
+        .map(|(name, command)| format!("{name}: {}", command.help))
+        .collect();
+    Ok(lines.join("\n"))
+}
+
+fn timescale(world: &mut World, args: &[&str]) -> Result<String, String> {
+    let mut time = world.resource_mut::<Time<Virtual>>();
+    if !args.is_empty() {
+        let speed: f32 = arg(args, 0, "speed")?;
+        if speed < 0.0 {
+            return Err("<speed> can not be negative".into());
+        }
+        time.set_relative_speed(speed);
+    }
+    Ok(format!("timescale {}", time.relative_speed()))
+}
+
+fn text_style(color: Color) -> TextStyle {
+    TextStyle {
+        font_size: 16.0,
+        color,
+        ..default()
+    }
+}
+
+fn spawn_console(mut cmds: Commands) {
+    cmds.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(40.),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::FlexEnd,
+                padding: UiRect::all(Val::Px(6.0)),
+                overflow: Overflow::clip(),
+                ..default()
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.85).into(),
+            z_index: ZIndex::Global(30),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        ConsoleRoot,
+    ))
+    .with_children(|root| {
+        root.spawn((TextBundle::default(), ConsoleLog));
+        root.spawn((
+            TextBundle::from_section("", text_style(TEXT_COLOR)),
+            ConsoleInput,
+        ));
+    });
+}
+
+fn show_console(
+    console: Res<Console>,
+    mut root_q: Query<&mut Visibility, With<ConsoleRoot>>,
+    mut log_q: Query<&mut Text, (With<ConsoleLog>, Without<ConsoleInput>)>,
+    mut input_q: Query<&mut Text, (With<ConsoleInput>, Without<ConsoleLog>)>,
+) {
+    if !console.is_changed() {
+        return;
+    }
+    for mut visibility in root_q.iter_mut() {
+        *visibility = if console.open {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+    let shown = console.log.len().saturating_sub(SHOWN_LINES);
+    for mut text in log_q.iter_mut() {
+        text.sections = console.log[shown..]
+            .iter()
+            .map(|(line, error)| {
+                let color = if *error { ERROR_COLOR } else { TEXT_COLOR };
+                TextSection::new(format!("{line}\n"), text_style(color))
+            })
+            .collect();
+    }
+    for mut text in input_q.iter_mut() {
+        text.sections[0].value = format!("> {}_", console.input);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spawn(_: &mut World, args: &[&str]) -> Result<String, String> {
+        Ok(format!("spawn {}", args.join(",")))
+    }
+
+    fn spawn_sentry(_: &mut World, args: &[&str]) -> Result<String, String> {
+        let level: u8 = arg(args, 0, "level")?;
+        Ok(format!("sentry {level}"))
+    }
+
+    fn commands() -> ConsoleCommands {
+        let mut commands = ConsoleCommands::default();
+        for (name, run) in [
+            ("spawn", spawn as ConsoleFn),
+            ("spawn sentry", spawn_sentry),
+        ] {
+            commands.0.insert(name, ConsoleCommand { help: "", run });
+        }
+        commands
+    }
+
+    /// lines printed for each submitted line, errors marked
+    fn run(lines: &[&str]) -> Vec<(String, bool)> {
+        let mut world = World::new();
+        world.insert_resource(commands());
+        world.init_resource::<Console>();
+        world.resource_mut::<Console>().submitted = lines.iter().map(|l| l.to_string()).collect();
+        run_commands(&mut world);
+        world
+            .resource::<Console>()
+            .log
+            .iter()
+            .filter(|(line, _)| !line.starts_with('>'))
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn longest_name_wins() {
+        let commands = commands();
+        let skip = |words: &[&str]| commands.find(words).map(|(_, skip)| skip);
+        assert_eq!(skip(&["spawn", "sentry", "3"]), Some(2));
+        assert_eq!(skip(&["spawn", "rock"]), Some(1));
+        assert_eq!(skip(&["spawn"]), Some(1));
+        assert_eq!(skip(&["spawnsentry"]), None);
+        assert_eq!(skip(&["sentry"]), None);
+    }
+
+    #[test]
+    fn lines_are_split_on_whitespace() {
+        let log = run(&["  spawn   sentry\t3 ", "spawn rock  big"]);
+        assert_eq!(
+            log,
+            [("sentry 3".into(), false), ("spawn rock,big".into(), false)]
+        );
+    }
+
+    #[test]
+    fn bad_arguments_are_explained() {
+        assert_eq!(arg::<u8>(&[], 0, "level"), Err("missing <level>".into()));
+        assert_eq!(
+            arg::<u8>(&["high"], 0, "level"),
+            Err("<level> can not be 'high'".into())
+        );
+        assert_eq!(arg::<u8>(&["a", "4"], 1, "level"), Ok(4));
+        let log = run(&["spawn sentry", "warp"]);
+        assert_eq!(
+            log,
+            [
+                ("missing <level>".into(), true),
+                ("unknown command 'warp', try help".into(), true)
+            ]
+        );
+    }
+}
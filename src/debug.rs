@@ -0,0 +1,195 @@
+//! developer overlay, toggled with F3
+//!
+//! draws zones, sentry sensors, hook joints and velocities with gizmos
+//! and counts the entities of each kind in a corner of the screen
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    astroids::Astroid,
+    gravity::GravityWell,
+    guns::{NinjaHook, Plasma},
+    launch::LaunchConfig,
+    pool::Parked,
+    sentry::{Detector, Sentry, Target, ThreatEvent},
+    ship::Player,
+    zones::{Zone, ZoneState, Zones},
+};
+
+const TOGGLE: KeyCode = KeyCode::F3;
+const SPAWNED_COLOR: Color = Color::srgba(0.3, 1.0, 0.3, 0.6);
+const DESPAWNED_COLOR: Color = Color::srgba(0.6, 0.6, 0.6, 0.3);
+const PLAYER_ZONE_COLOR: Color = Color::srgb(1.0, 1.0, 0.3);
+const DETECTOR_COLOR: Color = Color::srgba(1.0, 0.6, 0.2, 0.4);
+const THREAT_COLOR: Color = Color::srgb(1.0, 0.2, 0.2);
+const JOINT_COLOR: Color = Color::srgb(0.3, 0.6, 1.0);
+const VELOCITY_COLOR: Color = Color::srgba(0.9, 0.9, 0.9, 0.6);
+/// seconds of travel an arrow shows
+const VELOCITY_SCALE: f32 = 0.5;
+
+pub struct DebugPlug;
+
+impl Plugin for DebugPlug {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugOverlay>()
+            .register_type::<DebugOverlay>()
+            .add_systems(Startup, spawn_counter)
+            .add_systems(Update, toggle_overlay)
+            .add_systems(
+                PostUpdate,
+                (
+                    draw_zones,
+                    draw_detectors,
+                    draw_joints,
+                    draw_velocities,
+                    count_entities,
+                )
+                    .run_if(overlay_on),
+            );
+    }
+}
+
+#[derive(Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub struct DebugOverlay {
+    pub on: bool,
+}
+
+fn overlay_on(overlay: Res<DebugOverlay>) -> bool {
+    overlay.on
+}
+
+#[derive(Component)]
+struct EntityCounter;
+
+fn toggle_overlay(
+    mut overlay: ResMut<DebugOverlay>,
+    key_input: Res<ButtonInput<KeyCode>>,
+    mut counter_q: Query<&mut Visibility, With<EntityCounter>>,
+) {
+    if !key_input.just_pressed(TOGGLE) {
+        return;
+    }
+    overlay.on = !overlay.on;
+    for mut counter in counter_q.iter_mut() {
+        *counter = visibility(overlay.on);
+    }
+}
+
+fn visibility(on: bool) -> Visibility {
+    if on {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+/// hidden unless the game was launched with the overlay on
+fn spawn_counter(mut cmds: Commands, mut overlay: ResMut<DebugOverlay>, launch: Res<LaunchConfig>) {
+    overlay.on = launch.debug;
+    let style = TextStyle {
+        font_size: 16.0,
+        color: Color::srgb(0.9, 0.9, 0.9),
+        ..default()
+    };
+    cmds.spawn((
+        TextBundle::from_section("", style).with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(4.0),
+            right: Val::Px(8.0),
+            ..default()
+        }),
+        visibility(overlay.on),
+        EntityCounter,
+    ));
+}
+
+/// loaded zones in green, remembered ones in grey, the one the player is in highlighted
+fn draw_zones(mut gizmos: Gizmos, zones: Res<Zones>, player_q: Query<&Transform, With<Player>>) {
+    let size = Vec2::splat(Zone::SIZE * 2.0);
+    for (zone, state) in zones.state.iter() {
+        let color = match state {
+            ZoneState::Spawned => SPAWNED_COLOR,
+            ZoneState::Despawned(_) => DESPAWNED_COLOR,
+        };
+        gizmos.rect_2d(zone.center(), 0.0, size, color);
+    }
+    if let Ok(player) = player_q.get_single() {
+        let zone: Zone = player.translation.truncate().into();
+        gizmos.rect_2d(zone.center(), 0.0, size * 0.98, PLAYER_ZONE_COLOR);
+    }
+}
+
+/// sentry range and a line to everything it currently sees
+fn draw_detectors(
+    mut gizmos: Gizmos,
+    sentry_q: Query<(&Position, &Rotation, &Detector<Target>), With<Sentry>>,
+    mut threats: EventReader<ThreatEvent>,
+) {
+    for (position, _, detector) in sentry_q.iter() {
+        gizmos.circle_2d(**position, detector.range, DETECTOR_COLOR);
+    }
+    for ThreatEvent { sentry, threats } in threats.read() {
+        let Ok((position, rotation, _)) = sentry_q.get(*sentry) else {
+            continue;
+        };
+        for threat in threats.iter() {
+            let direction = Vec2::from_angle(threat.radians + rotation.as_radians());
+            let end = **position + direction * threat.distance;
+            gizmos.line_2d(**position, end, THREAT_COLOR);
+        }
+    }
+}
+
+/// hook ropes with a circle at their longest allowed length
+fn draw_joints(mut gizmos: Gizmos, joint_q: Query<&DistanceJoint>, q: Query<&Position>) {
+    for joint in joint_q.iter() {
+        let Ok([anchor, end]) = q.get_many([joint.entity1, joint.entity2]) else {
+            continue;
+        };
+        gizmos.line_2d(**anchor, **end, JOINT_COLOR);
+        if let Some(limits) = joint.length_limits {
+            gizmos.circle_2d(**anchor, limits.max, JOINT_COLOR);
+        }
+    }
+}
+
+fn draw_velocities(mut gizmos: Gizmos, q: Query<(&Position, &LinearVelocity), Without<Parked>>) {
+    for (position, velocity) in q.iter() {
+        if **velocity == Vec2::ZERO {
+            continue;
+        }
+        let end = **position + **velocity * VELOCITY_SCALE;
+        gizmos.arrow_2d(**position, end, VELOCITY_COLOR);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn count_entities(
+    all_q: Query<()>,
+    astroid_q: Query<(), (With<Astroid>, Without<Parked>)>,
+    sentry_q: Query<(), With<Sentry>>,
+    plasma_q: Query<(), (With<Plasma>, Without<Parked>)>,
+    hook_q: Query<(), With<NinjaHook>>,
+    well_q: Query<(), With<GravityWell>>,
+    parked_q: Query<(), With<Parked>>,
+    mut text_q: Query<&mut Text, With<EntityCounter>>,
+) {
+    let counts = [
+        ("entities", all_q.iter().count()),
+        ("astroids", astroid_q.iter().count()),
+        ("sentries", sentry_q.iter().count()),
+        ("plasma", plasma_q.iter().count()),
+        ("hooks", hook_q.iter().count()),
+        ("wells", well_q.iter().count()),
+        ("parked", parked_q.iter().count()),
+    ];
+    let lines: Vec<String> = counts
+        .iter()
+        .map(|(name, count)| format!("{name}: {count}"))
+        .collect();
+    for mut text in text_q.iter_mut() {
+        text.sections[0].value = lines.join("\n");
+    }
+}
@@ -1,12 +1,19 @@
 use bevy::prelude::*;
 
-use crate::{health::Health, ship::Player, state::GameState};
+use crate::{
+    assets::MyAssets,
+    health::Health,
+    persist::Persist,
+    pool::{park, Pooled},
+    ship::Player,
+    spatial::SpatialIndex,
+    state::GameState,
+};
 
 /// used for marking entity to not be faraway removed
 #[derive(Component)]
 pub struct Keep;
 
-pub const MAX_DISTANCE: f32 = 1000.0;
 pub struct DespawnPlugin;
 
 impl Plugin for DespawnPlugin {
@@ -15,24 +22,31 @@ impl Plugin for DespawnPlugin {
     }
 }
 
-fn remove_all(mut cmds: Commands, q: Query<Entity, With<Health>>) {
+fn remove_all(mut cmds: Commands, q: Query<Entity, Or<(With<Health>, With<Persist>)>>) {
     for ent in q.iter() {
         cmds.entity(ent).despawn_recursive();
     }
 }
 
-pub fn despawn_far<T: Component, const DIST: i32>(
+/// beyond the tuned max distance, only sees entities tracked by the [`SpatialIndex`]
+///
+/// pooled entities are parked for reuse like when they die
+pub fn despawn_far<T: Component>(
     mut cmds: Commands,
-    q: Query<(Entity, &Transform), With<T>>,
+    q: Query<Has<Pooled>, With<T>>,
     player_q: Query<&Transform, With<Player>>,
+    index: Res<SpatialIndex>,
+    assets: Res<MyAssets>,
 ) {
     let Ok(player) = player_q.get_single() else {
         return;
     };
-    for (ent, trans) in q.iter() {
-        let distance = trans.translation.distance(player.translation);
-        if distance > DIST as f32 {
-            cmds.entity(ent).despawn_recursive();
+    let dist = assets.tuning.max_distance;
+    for (ent, _) in index.outside(player.translation.truncate(), dist) {
+        match q.get(ent) {
+            Ok(true) => park(&mut cmds.entity(ent)),
+            Ok(false) => cmds.entity(ent).despawn_recursive(),
+            Err(_) => (),
         }
     }
 }
@@ -0,0 +1,85 @@
+use std::str::FromStr;
+
+use bevy::prelude::*;
+
+use crate::{launch::LaunchConfig, tuning::Tuning};
+
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Difficulty>()
+            .register_type::<Difficulty>()
+            .add_systems(Startup, apply_launch_difficulty);
+    }
+}
+
+#[derive(Resource, Reflect, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[reflect(Resource)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DifficultyParams {
+    /// share of the tuned ship health the player gets
+    pub ship_health: f32,
+    /// multiplies the damage the player ship takes
+    pub damage_taken: f32,
+    /// multiplies the sentries guarding zones away from the origin
+    pub sentries: f32,
+}
+
+impl Difficulty {
+    pub fn params(&self) -> DifficultyParams {
+        match self {
+            Difficulty::Easy => DifficultyParams {
+                ship_health: 2.0,
+                damage_taken: 0.5,
+                sentries: 0.5,
+            },
+            Difficulty::Normal => DifficultyParams {
+                ship_health: 1.0,
+                damage_taken: 1.0,
+                sentries: 1.0,
+            },
+            Difficulty::Hard => DifficultyParams {
+                ship_health: 0.5,
+                damage_taken: 1.5,
+                sentries: 2.0,
+            },
+        }
+    }
+
+    /// full health of the player ship
+    pub fn ship_health(&self, tuning: &Tuning) -> i32 {
+        (tuning.ship_health as f32 * self.params().ship_health) as i32
+    }
+
+    /// a hit on the player ship, never scaled down to nothing
+    pub fn damage_taken(&self, damage: i32) -> i32 {
+        ((damage as f32 * self.params().damage_taken).round() as i32).max(1)
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(()),
+        }
+    }
+}
+
+fn apply_launch_difficulty(launch: Res<LaunchConfig>, mut difficulty: ResMut<Difficulty>) {
+    if let Some(preset) = launch.difficulty {
+        *difficulty = preset;
+    }
+}
@@ -0,0 +1,217 @@
+use avian2d::prelude::*;
+use bevy::{ecs::entity::EntityHashSet, prelude::*, sprite::MaterialMesh2dBundle};
+use rand::Rng;
+
+use crate::{
+    assets::MyAssets,
+    collide_dmg::DamageEvent,
+    health::Death,
+    particles::{ParticleBurst, ParticleConfig},
+    schedule::InGameSet,
+};
+
+const FLASH_COLOR: Color = Color::srgb(4.0, 4.0, 4.0);
+const FLASH_TIME: f32 = 0.12;
+const NUMBER_TIME: f32 = 0.8;
+const NUMBER_SPEED: f32 = 8.0;
+/// text is rendered at this scale so it stays sharp under the zoomed in camera
+const NUMBER_SCALE: f32 = 0.1;
+
+pub struct FeedbackPlugin;
+
+impl Plugin for FeedbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (flash_on_hit, spawn_damage_numbers).in_set(InGameSet::EntityUpdate),
+        )
+        .add_systems(
+            Update,
+            (fade_flash, float_numbers, grow_fireball).in_set(InGameSet::EntityUpdate),
+        );
+    }
+}
+
+#[derive(Component)]
+struct HitFlash {
+    timer: Timer,
+    color: Color,
+}
+
+#[derive(Component)]
+struct FloatingNumber {
+    timer: Timer,
+}
+
+#[derive(Component)]
+struct Fireball {
+    timer: Timer,
+    radius: f32,
+}
+
+/// what a burst of debris looks like when something dies
+#[derive(Debug, Clone, Copy)]
+pub struct Blast {
+    pub particles: ParticleConfig,
+    pub count: usize,
+    /// radius of an expanding glow, none for a plain debris burst
+    pub fireball: Option<f32>,
+}
+
+/// visual counterpart to [`crate::health::DeathCry`]
+pub trait DeathBlast {
+    fn blast(&self) -> Blast;
+}
+
+pub fn blast_dead<T: Component + DeathBlast>(
+    mut cmds: Commands,
+    mut reader: EventReader<Death>,
+    mut bursts: EventWriter<ParticleBurst>,
+    q: Query<(&T, &Transform, Option<&LinearVelocity>)>,
+    assets: Res<MyAssets>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    for death in reader.read() {
+        let Ok((component, transform, velocity)) = q.get(**death) else {
+            continue;
+        };
+        let blast = component.blast();
+        let position = transform.translation.truncate();
+        bursts.send(ParticleBurst {
+            config: blast.particles,
+            count: blast.count,
+            position,
+            velocity: velocity.map(|v| v.0).unwrap_or_default(),
+        });
+
+        if let Some(radius) = blast.fireball {
+            cmds.spawn((
+                MaterialMesh2dBundle {
+                    mesh: assets.ball.clone().into(),
+                    material: materials.add(blast.particles.color.sample(0.0)),
+                    transform: Transform::from_translation(position.extend(4.0))
+                        .with_scale(Vec3::ZERO),
+                    ..default()
+                },
+                Fireball {
+                    timer: Timer::from_seconds(blast.particles.lifetime.1, TimerMode::Once),
+                    radius,
+                },
+            ));
+        }
+    }
+}
+
+fn flash_on_hit(
+    mut cmds: Commands,
+    mut reader: EventReader<DamageEvent>,
+    mut q: Query<(&mut Sprite, Option<&mut HitFlash>)>,
+) {
+    let hit: EntityHashSet = reader.read().map(|event| event.entity).collect();
+    for entity in hit {
+        let Ok((mut sprite, flash)) = q.get_mut(entity) else {
+            continue;
+        };
+        match flash {
+            Some(mut flash) => flash.timer.reset(),
+            None => {
+                cmds.entity(entity).try_insert(HitFlash {
+                    timer: Timer::from_seconds(FLASH_TIME, TimerMode::Once),
+                    color: sprite.color,
+                });
+            }
+        }
+        sprite.color = FLASH_COLOR;
+    }
+}
+
+fn fade_flash(
+    mut cmds: Commands,
+    mut q: Query<(Entity, &mut Sprite, &mut HitFlash)>,
+    time: Res<Time>,
+) {
+    for (entity, mut sprite, mut flash) in q.iter_mut() {
+        flash.timer.tick(time.delta());
+        if flash.timer.finished() {
+            sprite.color = flash.color;
+            cmds.entity(entity).remove::<HitFlash>();
+            continue;
+        }
+        sprite.color = FLASH_COLOR.mix(&flash.color, flash.timer.fraction());
+    }
+}
+
+fn spawn_damage_numbers(
+    mut cmds: Commands,
+    mut reader: EventReader<DamageEvent>,
+    q: Query<&Transform>,
+) {
+    let mut rng = rand::thread_rng();
+    for DamageEvent { entity, amount } in reader.read() {
+        let Ok(transform) = q.get(*entity) else {
+            continue;
+        };
+        let jitter = Vec2::new(rng.gen_range(-2.0..2.0), rng.gen_range(0.0..2.0));
+        let position = transform.translation.truncate() + jitter;
+        let style = TextStyle {
+            font_size: 32.0,
+            color: Color::srgb(2.0, 1.6, 0.4),
+            ..default()
+        };
+        cmds.spawn((
+            Text2dBundle {
+                text: Text::from_section(amount.to_string(), style),
+                transform: Transform::from_translation(position.extend(20.0))
+                    .with_scale(Vec3::splat(NUMBER_SCALE)),
+                ..default()
+            },
+            FloatingNumber {
+                timer: Timer::from_seconds(NUMBER_TIME, TimerMode::Once),
+            },
+        ));
+    }
+}
+
+fn float_numbers(
+    mut cmds: Commands,
+    mut q: Query<(Entity, &mut Transform, &mut Text, &mut FloatingNumber)>,
+    time: Res<Time>,
+) {
+    for (entity, mut transform, mut text, mut number) in q.iter_mut() {
+        number.timer.tick(time.delta());
+        if number.timer.finished() {
+            cmds.entity(entity).despawn_recursive();
+            continue;
+        }
+        transform.translation.y += NUMBER_SPEED * time.delta_seconds();
+        let alpha = 1.0 - number.timer.fraction();
+        for section in text.sections.iter_mut() {
+            section.style.color.set_alpha(alpha);
+        }
+    }
+}
+
+fn grow_fireball(
+    mut cmds: Commands,
+    mut q: Query<(
+        Entity,
+        &mut Transform,
+        &Handle<ColorMaterial>,
+        &mut Fireball,
+    )>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time>,
+) {
+    for (entity, mut transform, material, mut fireball) in q.iter_mut() {
+        fireball.timer.tick(time.delta());
+        if fireball.timer.finished() {
+            cmds.entity(entity).despawn_recursive();
+            continue;
+        }
+        let progress = fireball.timer.fraction();
+        transform.scale = Vec3::splat(fireball.radius * progress.sqrt());
+        if let Some(material) = materials.get_mut(material) {
+            material.color.set_alpha(1.0 - progress);
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use avian2d::prelude::*;
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+
+use crate::{
+    assets::MyAssets,
+    collide_dmg::CollisionDamage,
+    health::{Health, Invulnerable},
+    persist::{Persist, PersistApp, Restage},
+    radar::{scan, Blip, RadarBlip, RadarSet},
+    schedule::InGameSet,
+    ship::Player,
+    spatial::Indexed,
+    stage::Stage,
+};
+
+/// seconds of flight shown by the trajectory line
+const PREDICTION_TIME: f32 = 6.0;
+const PREDICTION_STEPS: usize = 180;
+const PREDICTION_COLOR: Color = Color::srgba(0.4, 0.8, 1.0, 0.5);
+
+pub struct GravityPlugin;
+
+impl Plugin for GravityPlugin {
+    fn build(&self, app: &mut App) {
+        // no down in space, only wells pull
+        app.insert_resource(Gravity(Vec2::ZERO))
+            .add_systems(
+                Update,
+                (attract, swallow).chain().in_set(InGameSet::EntityUpdate),
+            )
+            .add_systems(Update, predict_trajectory.after(InGameSet::EntityUpdate))
+            .add_systems(Update, scan::<GravityWell>.in_set(RadarSet::Scan))
+            .restage::<GravityWell>();
+    }
+}
+
+/// a massive body pulling on every dynamic body within reach
+#[derive(Component, Reflect, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[reflect(Component)]
+pub enum GravityWell {
+    #[default]
+    Planet,
+    /// kills anything that crosses its horizon
+    BlackHole,
+}
+
+impl GravityWell {
+    /// gravitational constant folded in, a circular orbit at r has speed sqrt(mass / r)
+    pub fn mass(&self) -> f32 {
+        match self {
+            GravityWell::Planet => 40_000.0,
+            GravityWell::BlackHole => 150_000.0,
+        }
+    }
+
+    /// solid surface of a planet, horizon of a black hole
+    pub fn radius(&self) -> f32 {
+        match self {
+            GravityWell::Planet => 25.0,
+            GravityWell::BlackHole => 6.0,
+        }
+    }
+
+    /// no pull beyond this, keeps wells local to their zone
+    pub fn reach(&self) -> f32 {
+        match self {
+            GravityWell::Planet => 300.0,
+            GravityWell::BlackHole => 400.0,
+        }
+    }
+
+    /// pull on something at offset from the center of the well
+    fn pull(&self, offset: Vec2) -> Vec2 {
+        let distance = offset.length();
+        if distance > self.reach() {
+            return Vec2::ZERO;
+        }
+        // inside the body the pull would blow up, hold it at surface strength
+        let distance = distance.max(self.radius());
+        -offset.normalize_or_zero() * self.mass() / distance.powi(2)
+    }
+
+    /// speed of a circular orbit at offset from the center, counter clockwise
+    pub fn orbit_velocity(&self, offset: Vec2) -> Vec2 {
+        let distance = offset.length().max(self.radius());
+        offset.normalize_or_zero().perp() * (self.mass() / distance).sqrt()
+    }
+}
+
+/// sum of the pull of all wells at a position
+fn acceleration(position: Vec2, wells: &[(GravityWell, Vec2)]) -> Vec2 {
+    wells
+        .iter()
+        .map(|(well, center)| well.pull(position - *center))
+        .sum()
+}
+
+fn wells(q: &Query<(&GravityWell, &Transform)>) -> Vec<(GravityWell, Vec2)> {
+    q.iter()
+        .map(|(well, transform)| (*well, transform.translation.truncate()))
+        .collect()
+}
+
+fn attract(
+    well_q: Query<(&GravityWell, &Transform)>,
+    mut q: Query<(&Transform, &mut LinearVelocity, &RigidBody), Without<GravityWell>>,
+    time: Res<Time>,
+) {
+    let wells = wells(&well_q);
+    if wells.is_empty() {
+        return;
+    }
+    let dt = time.delta_seconds();
+    for (transform, mut velocity, body) in q.iter_mut() {
+        if !body.is_dynamic() {
+            continue;
+        }
+        **velocity += acceleration(transform.translation.truncate(), &wells) * dt;
+    }
+}
+
+fn swallow(
+    well_q: Query<(&GravityWell, &Transform)>,
+    mut q: Query<(&Transform, &mut Health), (Without<GravityWell>, Without<Invulnerable>)>,
+) {
+    let holes: Vec<_> = wells(&well_q)
+        .into_iter()
+        .filter(|(well, _)| *well == GravityWell::BlackHole)
+        .collect();
+    for (transform, mut health) in q.iter_mut() {
+        let position = transform.translation.truncate();
+        let inside = holes
+            .iter()
+            .any(|(hole, center)| position.distance(*center) < hole.radius());
+        if inside && health.life > 0 {
+            health.life = 0;
+        }
+    }
+}
+
+/// where the ship drifts if the engines stay off
+fn predict_trajectory(
+    mut gizmos: Gizmos,
+    well_q: Query<(&GravityWell, &Transform)>,
+    player_q: Query<(&Transform, &LinearVelocity), With<Player>>,
+) {
+    let Ok((transform, velocity)) = player_q.get_single() else {
+        return;
+    };
+    let wells = wells(&well_q);
+    let mut position = transform.translation.truncate();
+    if acceleration(position, &wells) == Vec2::ZERO {
+        return;
+    }
+    let mut velocity = **velocity;
+    let dt = PREDICTION_TIME / PREDICTION_STEPS as f32;
+    let mut points = Vec::with_capacity(PREDICTION_STEPS + 1);
+    points.push(position);
+    for _ in 0..PREDICTION_STEPS {
+        velocity += acceleration(position, &wells) * dt;
+        position += velocity * dt;
+        points.push(position);
+        let crashed = wells
+            .iter()
+            .any(|(well, center)| position.distance(*center) < well.radius());
+        if crashed {
+            break;
+        }
+    }
+    gizmos.linestrip_2d(points, PREDICTION_COLOR);
+}
+
+impl RadarBlip for GravityWell {
+    fn blip(&self) -> Blip {
+        match self {
+            GravityWell::Planet => Blip {
+                color: Color::srgb(0.3, 0.7, 1.0),
+                size: 10.0,
+            },
+            GravityWell::BlackHole => Blip {
+                color: Color::srgb(0.6, 0.2, 0.9),
+                size: 10.0,
+            },
+        }
+    }
+}
+
+impl Stage for GravityWell {
+    fn stage(self, assets: &MyAssets, transform: Transform) -> impl Bundle {
+        let (mesh, material, layers) = match self {
+            GravityWell::Planet => (
+                assets.planet.clone(),
+                assets.planet_material.clone(),
+                CollisionLayers::default(),
+            ),
+            // nothing bounces off a black hole, it just goes in
+            GravityWell::BlackHole => (
+                assets.ball.clone(),
+                assets.black_hole_material.clone(),
+                CollisionLayers::NONE,
+            ),
+        };
+        let model = MaterialMesh2dBundle {
+            mesh: mesh.into(),
+            material,
+            transform: transform.with_scale(Vec3::splat(self.radius())),
+            ..default()
+        };
+        (
+            self,
+            Name::new("GravityWell"),
+            model,
+            RigidBody::Static,
+            Collider::circle(1.0),
+            layers,
+            CollisionDamage(5),
+            Persist,
+            Indexed,
+        )
+    }
+}
+
+impl Restage for GravityWell {
+    fn restage(&self, entity: &mut EntityWorldMut, assets: &MyAssets) {
+        entity.insert(self.stage(assets, Transform::default()));
+    }
+}
@@ -1,7 +1,7 @@
 mod ninja;
 mod plasma;
-pub use self::ninja::NinjaGun;
 use self::ninja::NinjaPlugin;
+pub use self::ninja::{NinjaGun, NinjaHook};
 // use self::plasma::Plasma;
 pub use self::plasma::{Plasma, PlasmaGun};
 
@@ -13,7 +13,10 @@ use bevy::sprite::Material2d;
 use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
 
 use crate::collide_dmg::CollisionDamage;
+use crate::feedback::blast_dead;
 use crate::health::cry_dead;
+use crate::pool::Pool;
+use crate::sound::{PlaySfx, Sfx};
 use crate::{
     assets::MyAssets, despawn::despawn_far, health::Health, schedule::InGameSet, ship::Player,
 };
@@ -29,8 +32,11 @@ impl Plugin for GunPlugin {
             cooldown_guns::<PlasmaGun>.in_set(InGameSet::EntityUpdate),
         )
         .add_systems(Update, handle_gun_fire::<PlasmaGun>)
-        .add_systems(Update, cry_dead::<Plasma>.in_set(InGameSet::Spawn))
-        .add_systems(Update, despawn_far::<Plasma, 10_000>);
+        .add_systems(
+            Update,
+            (cry_dead::<Plasma>, blast_dead::<Plasma>).in_set(InGameSet::Spawn),
+        )
+        .add_systems(Update, despawn_far::<Plasma>);
         app.add_event::<GunFireEvent<PlasmaGun>>();
         app.add_plugins(NinjaPlugin);
     }
@@ -61,16 +67,16 @@ pub struct GunFireEvent<G: FireCtrl> {
     pub transform: Transform,
 }
 
-trait SpawnMissle {
+trait SpawnMissle: FireCtrl {
     fn spawn_missle(
         &self,
         cmds: &mut Commands,
         velocity: &LinearVelocity,
         origin: Transform,
-        materials: &mut ResMut<Assets<ColorMaterial>>,
-        meshes: &mut ResMut<Assets<Mesh>>,
+        pool: &mut Pool<Self::Missle>,
         assets: &Res<MyAssets>,
     ) -> Entity;
+    fn pew(&self) -> Sfx;
 }
 
 trait Gun = FireCtrl + SpawnMissle + Component;
@@ -79,9 +85,9 @@ fn handle_gun_fire<G: Gun>(
     mut reader: EventReader<GunFireEvent<G>>,
     mut cmds: Commands,
     mut q: Query<(&mut G, &LinearVelocity, &Transform)>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    mut meshes: ResMut<Assets<Mesh>>,
+    mut pool: Pool<G::Missle>,
     assets: Res<MyAssets>,
+    mut sfx: EventWriter<PlaySfx>,
 ) {
     reader.read().for_each(|event| {
         let Ok(res) = q.get_mut(event.entity) else {
@@ -96,10 +102,13 @@ fn handle_gun_fire<G: Gun>(
             &mut cmds,
             ship_velocity,
             event.transform,
-            &mut materials,
-            &mut meshes,
+            &mut pool,
             &assets,
         );
+        sfx.send(PlaySfx::at(
+            gun.pew(),
+            event.transform.translation.truncate(),
+        ));
     });
 }
 
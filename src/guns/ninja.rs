@@ -1,12 +1,18 @@
 use std::borrow::BorrowMut;
 
-use crate::{collide_dmg::CollisionDamage, health::Health, schedule::InGameSet, ship::Player};
+use crate::{
+    camera::CameraImpulse,
+    collide_dmg::CollisionDamage,
+    health::Health,
+    schedule::InGameSet,
+    ship::Player,
+    sound::{PlaySfx, Sfx},
+};
 
 use super::{handle_gun_fire, FireCtrl, GunFireEvent, MyAssets, SpawnMissle};
 
 use avian2d::prelude::*;
 use bevy::{
-    audio,
     prelude::{Entity, *},
     sprite::{Material2d, MaterialMesh2dBundle},
 };
@@ -34,20 +40,6 @@ pub struct NinjaHook;
 impl NinjaHook {
     const SPEED: f32 = 120.0;
     const DENSITY: f32 = 5.0;
-    fn impact_sound(cmds: &mut Commands, assets: &Res<MyAssets>) {
-        let settings = PlaybackSettings {
-            mode: bevy::audio::PlaybackMode::Despawn,
-            speed: 1.5,
-            volume: audio::Volume::new(0.5),
-            ..Default::default()
-        };
-        let slap = AudioBundle {
-            source: assets.doing.clone(),
-            settings,
-        };
-
-        cmds.spawn(slap);
-    }
 }
 
 #[derive(Default)]
@@ -113,7 +105,8 @@ fn stick_on_collide(
     hook_q: Query<(Entity, &CollidingEntities, &Transform), (With<NinjaHook>, Without<Glue>)>,
     target_q: Query<(), Without<Sensor>>,
     player_q: Query<(Entity, &Transform), With<Player>>,
-    assets: Res<MyAssets>,
+    mut shake: EventWriter<CameraImpulse>,
+    mut sfx: EventWriter<PlaySfx>,
 ) {
     let Ok((hook_id, collisions, transform)) = hook_q.get_single() else {
         return;
@@ -135,15 +128,23 @@ fn stick_on_collide(
         .with_limits(0.0, distance)
         .with_compliance(1e-2);
     cmds.spawn(joint);
-    NinjaHook::impact_sound(&mut cmds, &assets);
+    sfx.send(PlaySfx::at(
+        Sfx::HookImpact,
+        transform.translation.truncate(),
+    ));
+    shake.send(CameraImpulse {
+        trauma: 0.3,
+        origin: None,
+    });
 }
 
 fn remove_long_hook(
     hook_q: Query<(&FromGun, &Transform), (With<NinjaHook>, Without<Glue>)>,
     gun_q: Query<&Transform, With<NinjaGun>>,
     mut writer: EventWriter<ReleaseHookEvent>,
+    assets: Res<MyAssets>,
 ) {
-    let max_dist = 130.0;
+    let max_dist = assets.tuning.hook_length;
     for (gun_id, hook_transform) in hook_q.iter() {
         let Ok(gun_transform) = gun_q.get(**gun_id) else {
             continue;
@@ -210,19 +211,14 @@ impl NinjaGun {
         cmds: &mut Commands,
         ship_velocity: &LinearVelocity,
         origin: Transform,
-        materials: &mut ResMut<Assets<ColorMaterial>>,
-        meshes: &mut ResMut<Assets<Mesh>>,
         assets: &Res<MyAssets>,
     ) {
         let radius = 0.5;
         let length = 2.;
-        let shape = Capsule2d::new(radius, length);
-        let color = Color::srgb(0., 0., 10.0);
-        let material = materials.add(color);
         let model = MaterialMesh2dBundle {
-            mesh: meshes.add(shape).into(),
+            mesh: assets.bolt.clone().into(),
             transform: origin,
-            material,
+            material: assets.hook_material.clone(),
             ..default()
         };
         let velocity: LinearVelocity =
@@ -239,32 +235,14 @@ impl NinjaGun {
         };
         let missle_id = cmds.spawn(missle).id();
         self.hook = Some(missle_id);
-        self.pew(cmds, assets);
-    }
-
-    fn pew(&self, cmds: &mut Commands, assets: &Res<MyAssets>) {
-        let settings = PlaybackSettings {
-            mode: bevy::audio::PlaybackMode::Despawn,
-            speed: 1.5,
-            volume: audio::Volume::new(0.3),
-            ..Default::default()
-        };
-        let pew_sound = AudioBundle {
-            source: assets.muffled_laser.clone(),
-            settings,
-        };
-        // audio
-
-        cmds.spawn(pew_sound);
     }
 }
 fn handle_hook_fire(
     mut reader: EventReader<GunFireEvent<NinjaGun>>,
     mut cmds: Commands,
     mut q: Query<(Entity, &mut NinjaGun, &LinearVelocity, &Transform)>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    mut meshes: ResMut<Assets<Mesh>>,
     assets: Res<MyAssets>,
+    mut sfx: EventWriter<PlaySfx>,
 ) {
     reader.read().for_each(|event| {
         let Ok(res) = q.get_mut(event.entity) else {
@@ -277,15 +255,8 @@ fn handle_hook_fire(
         };
         let mut origin = event.transform;
         origin.translation.z = 10.0;
-        gun.spawn_missle(
-            gun_id,
-            &mut cmds,
-            ship_velocity,
-            origin,
-            &mut materials,
-            &mut meshes,
-            &assets,
-        );
+        gun.spawn_missle(gun_id, &mut cmds, ship_velocity, origin, &assets);
+        sfx.send(PlaySfx::at(Sfx::HookLaunch, origin.translation.truncate()));
         // cmds.entity(event.entity).push_children(&[missle_id]);
     });
 }
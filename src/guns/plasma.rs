@@ -1,13 +1,18 @@
 use crate::{
     collide_dmg::CollisionDamage,
+    feedback::{Blast, DeathBlast},
     health::{DeathCry, Health},
     layers::GameLayer,
+    particles::ParticleConfig,
+    pool::Pool,
+    sound::Sfx,
+    spatial::Indexed,
 };
 
 use super::{FireCtrl, MissleBundle, MyAssets, SpawnMissle};
 
 use avian2d::prelude::*;
-use bevy::{audio::Volume, prelude::*, sprite::MaterialMesh2dBundle};
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
 
 #[derive(Component)]
 pub struct Plasma;
@@ -19,10 +24,17 @@ impl Plasma {
 }
 
 impl DeathCry for Plasma {
-    fn cry(&self, assets: &MyAssets) -> AudioBundle {
-        AudioBundle {
-            source: assets.pop.clone(),
-            settings: PlaybackSettings::DESPAWN,
+    fn cry(&self) -> Sfx {
+        Sfx::Pop
+    }
+}
+
+impl DeathBlast for Plasma {
+    fn blast(&self) -> Blast {
+        Blast {
+            particles: ParticleConfig::PLASMA_IMPACT,
+            count: 8,
+            fireball: None,
         }
     }
 }
@@ -33,23 +45,6 @@ pub struct PlasmaGun {
     count_down: f32,
 }
 
-impl PlasmaGun {
-    fn pew(&self, cmds: &mut Commands, assets: &Res<MyAssets>) {
-        let settings = PlaybackSettings {
-            mode: bevy::audio::PlaybackMode::Despawn,
-            speed: 1.5,
-            volume: Volume::new(0.3),
-            ..Default::default()
-        };
-        let pew_sound = AudioBundle {
-            source: assets.laser_sound.clone(),
-            settings,
-        };
-        // audio
-
-        cmds.spawn(pew_sound);
-    }
-}
 impl Default for PlasmaGun {
     fn default() -> Self {
         Self::new(Self::DEFAULT_RATE)
@@ -93,19 +88,15 @@ impl SpawnMissle for PlasmaGun {
         cmds: &mut Commands,
         ship_velocity: &LinearVelocity,
         origin: Transform,
-        materials: &mut ResMut<Assets<ColorMaterial>>,
-        meshes: &mut ResMut<Assets<Mesh>>,
+        pool: &mut Pool<Plasma>,
         assets: &Res<MyAssets>,
     ) -> Entity {
         let radius = 0.5;
         let length = 2.;
-        let shape = Capsule2d::new(radius, length);
-        let color = Color::srgb(7.5, 1.0, 7.5);
-        let material = materials.add(color);
         let model = MaterialMesh2dBundle {
-            mesh: meshes.add(shape).into(),
+            mesh: assets.bolt.clone().into(),
             transform: origin,
-            material,
+            material: assets.plasma_material.clone(),
             ..default()
         };
         let velocity: LinearVelocity =
@@ -130,8 +121,10 @@ impl SpawnMissle for PlasmaGun {
             filters,
         };
 
-        let id = cmds.spawn((Plasma, missle, layer)).id();
-        self.pew(cmds, assets);
-        id
+        pool.spawn(cmds, (Plasma, missle, layer, Indexed))
+    }
+
+    fn pew(&self) -> Sfx {
+        Sfx::Laser
     }
 }
@@ -2,8 +2,9 @@ use std::ops::{Deref, DerefMut};
 
 use bevy::prelude::*;
 
-use crate::assets::MyAssets;
+use crate::pool::{park, Parked, Pooled};
 use crate::schedule::InGameSet;
+use crate::sound::{PlaySfx, Sfx};
 
 pub struct HealthPlugin;
 
@@ -22,7 +23,7 @@ impl Plugin for HealthPlugin {
 #[derive(Event, Deref)]
 pub struct Death(Entity);
 
-fn detect_dead(mut writer: EventWriter<Death>, q: Query<(Entity, &Health)>) {
+fn detect_dead(mut writer: EventWriter<Death>, q: Query<(Entity, &Health), Without<Parked>>) {
     for (ent, Health { life, .. }) in q.iter() {
         if *life > 0 {
             continue;
@@ -31,32 +32,47 @@ fn detect_dead(mut writer: EventWriter<Death>, q: Query<(Entity, &Health)>) {
     }
 }
 
-fn despawn_dead(mut cmds: Commands, mut reader: EventReader<Death>) {
+fn despawn_dead(
+    mut cmds: Commands,
+    mut reader: EventReader<Death>,
+    pooled: Query<(), With<Pooled>>,
+) {
     for e in reader.read() {
-        cmds.entity(**e).despawn_recursive();
+        let Some(mut entity) = cmds.get_entity(**e) else {
+            continue;
+        };
+        if pooled.contains(**e) {
+            park(&mut entity);
+        } else {
+            entity.despawn_recursive();
+        }
     }
 }
 
 pub trait DeathCry {
-    fn cry(&self, assets: &MyAssets) -> AudioBundle;
+    fn cry(&self) -> Sfx;
 }
 
 pub fn cry_dead<T: Component + DeathCry>(
-    mut cmds: Commands,
+    mut sfx: EventWriter<PlaySfx>,
     mut reader: EventReader<Death>,
-    assets: Res<MyAssets>,
-    q: Query<&T>,
+    q: Query<(&T, &Transform)>,
 ) {
     for e in reader.read() {
-        let Ok(component) = q.get(**e) else {
+        let Ok((component, transform)) = q.get(**e) else {
             continue;
         };
-        let sound = component.cry(&assets);
-        cmds.spawn(sound);
+        let position = transform.translation.truncate();
+        sfx.send(PlaySfx::at(component.cry(), position));
     }
 }
 
-#[derive(Component, Default, Debug, Hash, PartialEq, Eq, Copy, Clone)]
+/// takes no damage and can not be swallowed, god mode from the console
+#[derive(Component, Default, Debug)]
+pub struct Invulnerable;
+
+#[derive(Component, Default, Debug, Hash, PartialEq, Eq, Copy, Clone, Reflect)]
+#[reflect(Component)]
 pub struct Health {
     pub life: i32,
     // pub death_cry: DeathCry,
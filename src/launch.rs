@@ -0,0 +1,197 @@
+//! options picked when starting the game
+//!
+//! natively they are flags like `--seed 42 --god`, on the web the same keys go in the
+//! page url like `?seed=42&god`
+
+use std::str::FromStr;
+
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    difficulty::Difficulty,
+    zones::{WorldSeed, Zone},
+};
+
+/// options followed by a value, all others are switches
+const TAKES_VALUE: [&str; 4] = ["seed", "zone", "difficulty", "size"];
+/// space between the ship and the surface of a well in the start zone
+const WELL_CLEARANCE: f32 = 200.0;
+
+pub struct LaunchPlugin;
+
+impl Plugin for LaunchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LaunchConfig>()
+            .add_systems(Startup, resize_window);
+    }
+}
+
+/// read once at startup by the plugins each option is about
+#[derive(Resource, Default, Debug, Clone)]
+pub struct LaunchConfig {
+    pub seed: Option<u64>,
+    /// zone the ship starts in instead of the origin, `--zone <row>,<col>`
+    pub zone: Option<Zone>,
+    pub difficulty: Option<Difficulty>,
+    pub god: bool,
+    /// start with the debug overlay on
+    pub debug: bool,
+    /// `--size <width>x<height>`
+    pub window_size: Option<Vec2>,
+    pub mute: bool,
+}
+
+impl LaunchConfig {
+    /// from the command line, or the page url on the web
+    pub fn from_env() -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        return Self::from_args(std::env::args().skip(1));
+        #[cfg(target_arch = "wasm32")]
+        return Self::from_query(&page_query());
+    }
+
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Self {
+        let mut options = Vec::new();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let Some(key) = arg.strip_prefix("--") else {
+                warn!("launch argument '{arg}' ignored, options start with --");
+                continue;
+            };
+            let value = TAKES_VALUE.contains(&key).then(|| args.next()).flatten();
+            options.push((key.to_string(), value));
+        }
+        Self::from_options(options)
+    }
+
+    /// `seed=42&god`, a leading `?` is fine
+    pub fn from_query(query: &str) -> Self {
+        let options = query
+            .trim_start_matches('?')
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| match pair.split_once('=') {
+                Some((key, value)) => (key.to_string(), Some(value.to_string())),
+                None => (pair.to_string(), None),
+            });
+        Self::from_options(options)
+    }
+
+    /// a bad option is reported and left out, the game still starts
+    fn from_options(options: impl IntoIterator<Item = (String, Option<String>)>) -> Self {
+        let mut config = Self::default();
+        for (key, value) in options {
+            if let Err(err) = config.set(&key, value.as_deref()) {
+                warn!("launch option {key}: {err}");
+            }
+        }
+        config
+    }
+
+    fn set(&mut self, key: &str, value: Option<&str>) -> Result<(), String> {
+        let value = || value.ok_or("needs a value".to_string());
+        match key {
+            "seed" => self.seed = Some(parse(value()?)?),
+            "zone" => {
+                let (row, col) = value()?.split_once(',').ok_or("expected <row>,<col>")?;
+                self.zone = Some(Zone::new(parse(row)?, parse(col)?));
+            }
+            "difficulty" => self.difficulty = Some(parse(value()?)?),
+            "god" => self.god = true,
+            "debug" if !cfg!(feature = "debug") => {
+                return Err("this build has no debug overlay".into())
+            }
+            "debug" => self.debug = true,
+            "size" => {
+                let (width, height) = value()?
+                    .split_once('x')
+                    .ok_or("expected <width>x<height>")?;
+                self.window_size = Some(Vec2::new(parse(width)?, parse(height)?));
+            }
+            "mute" => self.mute = true,
+            _ => return Err("no such option".into()),
+        }
+        Ok(())
+    }
+
+    /// where the ship starts, to the side of the well when the zone has one
+    pub fn start(&self, world: WorldSeed) -> Vec2 {
+        let zone = self.zone.unwrap_or_default();
+        let offset = zone
+            .well(world)
+            .map_or(0.0, |well| well.radius() + WELL_CLEARANCE);
+        zone.center() + Vec2::new(offset, 0.0)
+    }
+}
+
+fn parse<T: FromStr>(raw: &str) -> Result<T, String> {
+    raw.parse().map_err(|_| format!("can not read '{raw}'"))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn page_query() -> String {
+    web_sys::window()
+        .and_then(|window| window.location().search().ok())
+        .unwrap_or_default()
+}
+
+fn resize_window(launch: Res<LaunchConfig>, mut q: Query<&mut Window, With<PrimaryWindow>>) {
+    let Some(size) = launch.window_size else {
+        return;
+    };
+    for mut window in q.iter_mut() {
+        window.resolution.set(size.x, size.y);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> LaunchConfig {
+        LaunchConfig::from_args(line.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn flags() {
+        let config = args("--seed 42 --zone 2,-3 --difficulty hard --size 800x600 --god --mute");
+        assert_eq!(config.seed, Some(42));
+        assert_eq!(config.zone, Some(Zone::new(2, -3)));
+        assert_eq!(config.difficulty, Some(Difficulty::Hard));
+        assert_eq!(config.window_size, Some(Vec2::new(800.0, 600.0)));
+        assert!(config.god);
+        assert!(config.mute);
+    }
+
+    #[test]
+    fn query() {
+        let config = LaunchConfig::from_query("?seed=7&god&difficulty=easy&&zone=0,1");
+        assert_eq!(config.seed, Some(7));
+        assert!(config.god);
+        assert_eq!(config.difficulty, Some(Difficulty::Easy));
+        assert_eq!(config.zone, Some(Zone::new(0, 1)));
+        assert!(!config.mute);
+    }
+
+    #[test]
+    fn bad_values_are_left_out() {
+        let config = args("--seed -1 --zone 3 --difficulty brutal --size 800 stray --nope --god");
+        assert_eq!(config.seed, None);
+        assert_eq!(config.zone, None);
+        assert_eq!(config.difficulty, None);
+        assert_eq!(config.window_size, None);
+        assert!(config.god);
+
+        let config = LaunchConfig::from_query("seed&zone=a,b&mute=yes");
+        assert_eq!(config.seed, None);
+        assert_eq!(config.zone, None);
+        assert!(config.mute);
+    }
+
+    #[test]
+    fn missing_value_at_the_end() {
+        let config = args("--god --seed");
+        assert!(config.god);
+        assert_eq!(config.seed, None);
+    }
+}
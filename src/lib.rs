@@ -3,32 +3,125 @@
 
 pub mod assets;
 pub mod astroids;
+pub mod biome;
 pub mod camera;
 pub mod collide_dmg;
+pub mod console;
+pub mod debug;
 pub mod despawn;
+pub mod difficulty;
+pub mod feedback;
+pub mod gravity;
 pub mod guns;
 pub mod health;
+pub mod launch;
 pub mod layers;
+pub mod loading;
+pub mod map;
+pub mod menu;
+pub mod music;
+pub mod particles;
+pub mod persist;
+pub mod pool;
+pub mod radar;
 pub mod schedule;
 pub mod sentry;
 pub mod ship;
+pub mod sound;
+pub mod spatial;
 pub mod stage;
 pub mod state;
+pub mod tuning;
 pub mod ui;
 pub mod zones;
 
-use bevy::prelude::*;
-use schedule::InGameSet;
+use avian2d::prelude::*;
+use bevy::{app::PluginGroupBuilder, prelude::*};
 
-pub struct DebugPlug;
+/// the whole game, engine plugins set up for the platform picked with cargo features
+pub struct GamePlugins;
 
-fn print_position(q: Query<(Entity, &Transform)>) {
-    for (entity, position) in q.iter() {
-        info!("Enity {:?} is at position {:?}", entity, position)
+impl PluginGroup for GamePlugins {
+    fn build(self) -> PluginGroupBuilder {
+        let group = PluginGroupBuilder::start::<Self>()
+            .add_group(default_plugins())
+            .add_group(PhysicsPlugins::default().with_length_unit(1.))
+            .add(launch::LaunchPlugin)
+            .add(difficulty::DifficultyPlugin)
+            .add(collide_dmg::CollideDamagePlugin)
+            .add(state::StatePlugin)
+            .add(schedule::SchedulePlugin)
+            .add(assets::AssetPlug)
+            .add(tuning::TuningPlugin)
+            .add(loading::LoadingPlugin)
+            .add(sound::SoundPlugin)
+            .add(music::MusicPlugin)
+            .add(health::HealthPlugin)
+            .add(ship::ShipPlug)
+            .add(astroids::AstriodPlug)
+            .add(guns::GunPlugin)
+            .add(despawn::DespawnPlugin)
+            .add(zones::ZonePlugin)
+            .add(persist::PersistPlugin)
+            .add(spatial::SpatialPlugin)
+            .add(sentry::SentryPlugin)
+            .add(gravity::GravityPlugin)
+            .add(particles::ParticlePlugin)
+            .add(feedback::FeedbackPlugin)
+            .add(ui::UIPlugin)
+            .add(radar::RadarPlugin)
+            .add(map::MapPlugin)
+            .add(menu::MenuPlugin)
+            .add(camera::CameraPlugin);
+
+        #[cfg(feature = "debug")]
+        let group = group.add(debug::DebugPlug).add(console::ConsolePlugin);
+
+        #[cfg(feature = "inspector")]
+        let group = group.add(bevy_inspector_egui::quick::WorldInspectorPlugin::new());
+
+        group
     }
 }
-impl Plugin for DebugPlug {
-    fn build(&self, app: &mut App) {
-        app.add_systems(Update, print_position.after(InGameSet::EntityUpdate));
-    }
+
+#[cfg(feature = "headless")]
+fn default_plugins() -> PluginGroupBuilder {
+    use bevy::{
+        render::{settings::WgpuSettings, RenderPlugin},
+        window::ExitCondition,
+        winit::WinitPlugin,
+    };
+
+    DefaultPlugins
+        .set(WindowPlugin {
+            primary_window: None,
+            exit_condition: ExitCondition::DontExit,
+            close_when_requested: false,
+        })
+        .set(RenderPlugin {
+            render_creation: WgpuSettings {
+                backends: None,
+                ..default()
+            }
+            .into(),
+            ..default()
+        })
+        .disable::<WinitPlugin>()
+}
+
+#[cfg(all(feature = "web", not(feature = "headless")))]
+fn default_plugins() -> PluginGroupBuilder {
+    DefaultPlugins.set(WindowPlugin {
+        primary_window: Some(Window {
+            // the canvas on the page in docs/
+            canvas: Some("#game".into()),
+            ..default()
+        }),
+        ..default()
+    })
+}
+
+#[cfg(not(any(feature = "web", feature = "headless")))]
+fn default_plugins() -> PluginGroupBuilder {
+    DefaultPlugins.build()
 }
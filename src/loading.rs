@@ -0,0 +1,250 @@
+use bevy::{
+    asset::{LoadState, RecursiveDependencyLoadState},
+    prelude::*,
+};
+
+use crate::{
+    assets::{Manifest, MyAssets},
+    schedule::InitStages,
+    state::GameState,
+    tuning::TuningFile,
+};
+
+const MANIFEST: &str = "game.manifest.ron";
+const BAR_WIDTH: f32 = 300.0;
+const BAR_COLOR: Color = Color::srgb(0.3, 0.8, 0.3);
+const FAILED_COLOR: Color = Color::srgb(1.0, 0.3, 0.3);
+
+pub struct LoadingPlugin;
+
+impl Plugin for LoadingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LoadingAssets>()
+            .add_systems(Startup, load_manifest.in_set(InitStages::LoadAssets))
+            .add_systems(OnEnter(GameState::Loading), open_loading_screen)
+            .add_systems(OnExit(GameState::Loading), close_loading_screen)
+            .add_systems(
+                Update,
+                (queue_assets, track_assets, show_progress)
+                    .chain()
+                    .run_if(in_state(GameState::Loading)),
+            );
+    }
+}
+
+/// what the loading screen is waiting for
+#[derive(Resource, Default)]
+struct LoadingAssets {
+    manifest: Handle<Manifest>,
+    /// set once the manifest is read and its files are on the way
+    queued: bool,
+    pending: Vec<UntypedHandle>,
+    total: usize,
+    /// every file that could not be loaded, with the reason
+    failed: Vec<String>,
+}
+
+impl LoadingAssets {
+    fn progress(&self) -> f32 {
+        if !self.queued || self.total == 0 {
+            return 0.0;
+        }
+        (self.total - self.pending.len()) as f32 / self.total as f32
+    }
+
+    /// everything has loaded or failed, and some failed
+    fn stuck(&self) -> bool {
+        self.queued && self.pending.is_empty() && !self.failed.is_empty()
+    }
+}
+
+#[derive(Component)]
+struct LoadingScreen;
+
+#[derive(Component)]
+struct ProgressFill;
+
+#[derive(Component)]
+struct ProgressText;
+
+#[derive(Component)]
+struct FailedText;
+
+fn load_manifest(mut loading: ResMut<LoadingAssets>, server: Res<AssetServer>) {
+    loading.manifest = server.load(MANIFEST);
+}
+
+fn queue_assets(
+    mut loading: ResMut<LoadingAssets>,
+    mut assets: ResMut<MyAssets>,
+    manifests: Res<Assets<Manifest>>,
+    tuning: Res<TuningFile>,
+    server: Res<AssetServer>,
+) {
+    if loading.queued {
+        return;
+    }
+    let manifest = match server.get_load_state(&loading.manifest) {
+        Some(LoadState::Loaded) => manifests.get(&loading.manifest),
+        Some(LoadState::Failed(err)) => {
+            error!("asset manifest {MANIFEST} failed to load: {err}");
+            loading.failed.push(format!("{MANIFEST}: {err}"));
+            None
+        }
+        _ => return,
+    };
+    loading.queued = true;
+    // gameplay numbers have to be in before anything spawns
+    loading.pending.push(tuning.0.clone().untyped());
+    let loads = manifest
+        .map(|manifest| assets.load_manifest(manifest, &server))
+        .unwrap_or_default();
+    for load in loads {
+        match load {
+            Ok(handle) => loading.pending.push(handle),
+            Err(err) => {
+                error!("asset manifest {MANIFEST}: {err}");
+                loading.failed.push(err);
+            }
+        }
+    }
+    loading.total = loading.pending.len();
+}
+
+fn track_assets(
+    mut loading: ResMut<LoadingAssets>,
+    server: Res<AssetServer>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut next: ResMut<NextState<GameState>>,
+) {
+    if !loading.queued {
+        return;
+    }
+    let LoadingAssets {
+        pending, failed, ..
+    } = &mut *loading;
+    pending.retain(|handle| match server.get_load_state(handle.id()) {
+        Some(LoadState::Failed(err)) => {
+            let path = handle.path().map(ToString::to_string).unwrap_or_default();
+            error!("failed to load {path}: {err}");
+            failed.push(format!("{path}: {err}"));
+            false
+        }
+        // a scene never finishes when one of its textures fails
+        _ if server.get_recursive_dependency_load_state(handle.id())
+            == Some(RecursiveDependencyLoadState::Failed) =>
+        {
+            let path = handle.path().map(ToString::to_string).unwrap_or_default();
+            error!("failed to load a dependency of {path}");
+            failed.push(format!("{path}: a dependency failed to load"));
+            false
+        }
+        _ => !server.is_loaded_with_dependencies(handle.id()),
+    });
+    if !pending.is_empty() {
+        return;
+    }
+    if failed.is_empty() {
+        next.set(GameState::Play);
+    } else if keys.get_just_pressed().next().is_some() {
+        // the failures stay on screen until the player has seen them
+        warn!("starting with {} assets missing", failed.len());
+        next.set(GameState::Play);
+    }
+}
+
+fn text_style(font_size: f32, color: Color) -> TextStyle {
+    TextStyle {
+        font_size,
+        color,
+        ..default()
+    }
+}
+
+fn open_loading_screen(mut cmds: Commands) {
+    cmds.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            background_color: Color::BLACK.into(),
+            z_index: ZIndex::Global(20),
+            ..default()
+        },
+        LoadingScreen,
+    ))
+    .with_children(|screen| {
+        let white = Color::srgb(0.9, 0.9, 0.9);
+        screen.spawn(TextBundle::from_section("LOADING", text_style(48.0, white)));
+        screen
+            .spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(BAR_WIDTH),
+                    height: Val::Px(16.0),
+                    ..default()
+                },
+                background_color: Color::srgb(0.15, 0.15, 0.15).into(),
+                ..default()
+            })
+            .with_children(|bar| {
+                bar.spawn((
+                    NodeBundle {
+                        style: Style {
+                            width: Val::Percent(0.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        background_color: BAR_COLOR.into(),
+                        ..default()
+                    },
+                    ProgressFill,
+                ));
+            });
+        screen.spawn((
+            TextBundle::from_section("", text_style(18.0, white)),
+            ProgressText,
+        ));
+        screen.spawn((
+            TextBundle::from_section("", text_style(16.0, FAILED_COLOR)),
+            FailedText,
+        ));
+    });
+}
+
+fn close_loading_screen(mut cmds: Commands, q: Query<Entity, With<LoadingScreen>>) {
+    for screen in q.iter() {
+        cmds.entity(screen).despawn_recursive();
+    }
+}
+
+fn show_progress(
+    loading: Res<LoadingAssets>,
+    mut fill_q: Query<&mut Style, With<ProgressFill>>,
+    mut text_q: Query<&mut Text, (With<ProgressText>, Without<FailedText>)>,
+    mut failed_q: Query<&mut Text, (With<FailedText>, Without<ProgressText>)>,
+) {
+    for mut style in fill_q.iter_mut() {
+        style.width = Val::Percent(loading.progress() * 100.0);
+    }
+    let done = loading.total - loading.pending.len();
+    for mut text in text_q.iter_mut() {
+        text.sections[0].value = if loading.stuck() {
+            format!(
+                "{} could not be loaded, press any key to play without them",
+                loading.failed.len()
+            )
+        } else {
+            format!("{done} / {}", loading.total)
+        };
+    }
+    for mut text in failed_q.iter_mut() {
+        text.sections[0].value = loading.failed.join("\n");
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
+    prelude::*,
+};
+
+use crate::{
+    astroids::Rock,
+    biome::Biome,
+    difficulty::Difficulty,
+    schedule::InGameSet,
+    ship::Player,
+    state::GameState,
+    zones::{Population, WorldSeed, Zone, ZoneState, Zones},
+};
+
+/// world distance between recorded trail points
+const TRAIL_STEP: f32 = 40.0;
+const TRAIL_LENGTH: usize = 5000;
+/// pan speed in screen pixels per second
+const PAN_SPEED: f32 = 400.0;
+const MIN_SCALE: f32 = 0.005;
+const MAX_SCALE: f32 = 0.5;
+/// populations at least this big are marked on the map
+const DENSE_POPULATION: u32 = 70;
+/// metal rocks needed for a zone to count as rich
+const RICH_METAL: u32 = 12;
+
+const BACKDROP: Color = Color::srgba(0.0, 0.0, 0.02, 0.95);
+/// zones are tinted by biome, loaded ones brighter than the ones left behind
+const EXPLORED_ALPHA: f32 = 0.45;
+const LOADED_ALPHA: f32 = 0.9;
+const TRAIL_COLOR: Color = Color::srgb(0.9, 0.9, 0.3);
+const DENSE_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const RICH_COLOR: Color = Color::srgb(1.0, 0.5, 0.2);
+
+pub struct MapPlugin;
+
+impl Plugin for MapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Trail>()
+            .init_resource::<MapView>()
+            .add_systems(Update, record_trail.in_set(InGameSet::EntityUpdate))
+            .add_systems(OnEnter(GameState::GameOver), clear_trail)
+            .add_systems(OnEnter(GameState::Map), open_map)
+            .add_systems(OnExit(GameState::Map), close_map)
+            .add_systems(
+                Update,
+                (map_ctrl, layout_map)
+                    .chain()
+                    .run_if(in_state(GameState::Map)),
+            );
+    }
+}
+
+/// where the player has been, oldest first
+#[derive(Resource, Default)]
+pub struct Trail(VecDeque<Vec2>);
+
+/// part of the world the map is looking at
+#[derive(Resource)]
+struct MapView {
+    center: Vec2,
+    /// screen pixels per world unit
+    scale: f32,
+}
+
+impl Default for MapView {
+    fn default() -> Self {
+        Self {
+            center: Vec2::ZERO,
+            scale: 0.04,
+        }
+    }
+}
+
+#[derive(Component)]
+struct MapScreen;
+
+/// something drawn on the map at a world position
+#[derive(Component)]
+struct MapItem {
+    position: Vec2,
+    size: MapSize,
+}
+
+enum MapSize {
+    /// world units, grows and shrinks with zoom
+    World(f32),
+    /// screen pixels
+    Fixed(f32),
+}
+
+fn record_trail(mut trail: ResMut<Trail>, q: Query<&Transform, With<Player>>) {
+    let Ok(player) = q.get_single() else {
+        return;
+    };
+    let position = player.translation.truncate();
+    let moved = trail
+        .0
+        .back()
+        .is_none_or(|last| last.distance(position) > TRAIL_STEP);
+    if !moved {
+        return;
+    }
+    if trail.0.len() >= TRAIL_LENGTH {
+        trail.0.pop_front();
+    }
+    trail.0.push_back(position);
+}
+
+fn clear_trail(mut trail: ResMut<Trail>) {
+    trail.0.clear();
+}
+
+fn square(color: Color, marks: MapItem) -> (NodeBundle, MapItem) {
+    let node = NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            ..default()
+        },
+        background_color: color.into(),
+        ..default()
+    };
+    (node, marks)
+}
+
+fn open_map(
+    mut cmds: Commands,
+    mut view: ResMut<MapView>,
+    zones: Res<Zones>,
+    world: Res<WorldSeed>,
+    difficulty: Res<Difficulty>,
+    trail: Res<Trail>,
+    player_q: Query<&Transform, With<Player>>,
+) {
+    let player = player_q
+        .get_single()
+        .map(|t| t.translation.truncate())
+        .unwrap_or_default();
+    view.center = player;
+
+    let text_style = TextStyle {
+        font_size: 20.0,
+        color: Color::srgb(0.9, 0.9, 0.9),
+        ..default()
+    };
+    cmds.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                overflow: Overflow::clip(),
+                ..default()
+            },
+            background_color: BACKDROP.into(),
+            z_index: ZIndex::Global(10),
+            ..default()
+        },
+        MapScreen,
+    ))
+    .with_children(|map| {
+        // only what the player has seen, not zones that strays drifted into
+        let seen = zones
+            .state
+            .iter()
+            .filter(|(zone, _)| zones.visited.contains(*zone));
+        for (zone, state) in seen {
+            let alpha = match state {
+                ZoneState::Spawned => LOADED_ALPHA,
+                ZoneState::Despawned(_) => EXPLORED_ALPHA,
+            };
+            let color = Biome::at(*zone, *world).color().with_alpha(alpha);
+            let cell = MapItem {
+                position: zone.center(),
+                size: MapSize::World(Zone::SIZE * 2.0 * 0.95),
+            };
+            map.spawn(square(color, cell));
+
+            let generated;
+            let population = match state {
+                ZoneState::Despawned(pop) => pop,
+                ZoneState::Spawned => {
+                    generated = Population::generate(*zone, *world, *difficulty);
+                    &generated
+                }
+            };
+            let mut marks = Vec::new();
+            if population.size() >= DENSE_POPULATION {
+                marks.push(DENSE_COLOR);
+            }
+            if population.count_kind(Rock::Metal) >= RICH_METAL {
+                marks.push(RICH_COLOR);
+            }
+            for (i, color) in marks.into_iter().enumerate() {
+                let offset = Vec2::new(i as f32 * 0.3 - 0.15, 0.0) * Zone::SIZE;
+                let mark = MapItem {
+                    position: zone.center() + offset,
+                    size: MapSize::World(Zone::SIZE * 0.25),
+                };
+                map.spawn(square(color, mark));
+            }
+        }
+
+        for point in trail.0.iter() {
+            let dot = MapItem {
+                position: *point,
+                size: MapSize::Fixed(3.0),
+            };
+            map.spawn(square(TRAIL_COLOR, dot));
+        }
+
+        let ship = MapItem {
+            position: player,
+            size: MapSize::Fixed(10.0),
+        };
+        map.spawn(square(Color::WHITE, ship));
+
+        map.spawn(TextBundle::from_section(
+            "MAP   [M] close   [arrows / drag] pan   [wheel / + -] zoom",
+            text_style,
+        ));
+    });
+}
+
+fn close_map(mut cmds: Commands, q: Query<Entity, With<MapScreen>>) {
+    for map in q.iter() {
+        cmds.entity(map).despawn_recursive();
+    }
+}
+
+fn map_ctrl(
+    mut view: ResMut<MapView>,
+    keys: Res<ButtonInput<KeyCode>>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    mut motion: EventReader<MouseMotion>,
+    mut wheel: EventReader<MouseWheel>,
+    // game time is paused while the map is open
+    time: Res<Time<Real>>,
+) {
+    let mut pan = Vec2::ZERO;
+    if keys.pressed(KeyCode::ArrowLeft) {
+        pan.x -= 1.0;
+    }
+    if keys.pressed(KeyCode::ArrowRight) {
+        pan.x += 1.0;
+    }
+    if keys.pressed(KeyCode::ArrowUp) {
+        pan.y += 1.0;
+    }
+    if keys.pressed(KeyCode::ArrowDown) {
+        pan.y -= 1.0;
+    }
+    let mut screen_pan = pan * PAN_SPEED * time.delta_seconds();
+    if buttons.pressed(MouseButton::Left) {
+        for event in motion.read() {
+            screen_pan -= Vec2::new(event.delta.x, -event.delta.y);
+        }
+    } else {
+        motion.clear();
+    }
+    let scale = view.scale;
+    view.center += screen_pan / scale;
+
+    let mut zoom = 1.0;
+    for event in wheel.read() {
+        let notches = match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / 100.0,
+        };
+        zoom *= 1.0 + notches * 0.1;
+    }
+    if keys.pressed(KeyCode::Equal) || keys.pressed(KeyCode::NumpadAdd) {
+        zoom *= 1.0 + time.delta_seconds();
+    }
+    if keys.pressed(KeyCode::Minus) || keys.pressed(KeyCode::NumpadSubtract) {
+        zoom /= 1.0 + time.delta_seconds();
+    }
+    view.scale = (view.scale * zoom).clamp(MIN_SCALE, MAX_SCALE);
+}
+
+fn layout_map(view: Res<MapView>, window_q: Query<&Window>, mut q: Query<(&MapItem, &mut Style)>) {
+    let Ok(window) = window_q.get_single() else {
+        return;
+    };
+    let half_screen = Vec2::new(window.width(), window.height()) / 2.0;
+    for (item, mut style) in q.iter_mut() {
+        let size = match item.size {
+            MapSize::World(size) => size * view.scale,
+            MapSize::Fixed(size) => size,
+        };
+        let offset = (item.position - view.center) * view.scale;
+        let screen = half_screen + Vec2::new(offset.x, -offset.y) - size / 2.0;
+        style.left = Val::Px(screen.x);
+        style.top = Val::Px(screen.y);
+        style.width = Val::Px(size);
+        style.height = Val::Px(size);
+    }
+}
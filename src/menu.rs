@@ -0,0 +1,274 @@
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+};
+
+use crate::{
+    camera::ShakeSettings,
+    sound::{Bus, PlaySfx, Sfx, VolumeSettings},
+    state::GameState,
+    zones::WorldSeed,
+};
+
+const BACKDROP: Color = Color::srgba(0.0, 0.0, 0.0, 0.8);
+const BUTTON_COLOR: Color = Color::srgb(0.15, 0.15, 0.15);
+const HOVERED_COLOR: Color = Color::srgb(0.25, 0.25, 0.25);
+const FIELD_COLOR: Color = Color::srgb(0.05, 0.1, 0.05);
+/// digits that fit in a u64
+const MAX_SEED_DIGITS: usize = 19;
+const VOLUME_STEP: f32 = 0.1;
+const SHAKE_STEP: f32 = 0.25;
+
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SeedDraft>()
+            .add_systems(OnEnter(GameState::Paused), open_menu)
+            .add_systems(OnExit(GameState::Paused), close_menu)
+            .add_systems(
+                Update,
+                (type_seed, menu_buttons, show_draft, show_volume, show_shake)
+                    .chain()
+                    .run_if(in_state(GameState::Paused)),
+            );
+    }
+}
+
+/// seed being typed in the menu, applied when a new universe is started
+#[derive(Resource, Default)]
+struct SeedDraft(String);
+
+#[derive(Component)]
+struct MenuScreen;
+
+#[derive(Component)]
+struct DraftText;
+
+#[derive(Component)]
+struct VolumeText(Bus);
+
+#[derive(Component)]
+struct ShakeText;
+
+#[derive(Component, Clone, Copy)]
+enum MenuButton {
+    Resume,
+    RandomSeed,
+    NewUniverse,
+    Volume(Bus, f32),
+    Shake(f32),
+}
+
+fn menu_button(parent: &mut ChildBuilder, label: &str, action: MenuButton) {
+    sized_button(parent, label, action, 260.0);
+}
+
+fn sized_button(parent: &mut ChildBuilder, label: &str, action: MenuButton, width: f32) {
+    parent
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    width: Val::Px(width),
+                    padding: UiRect::all(Val::Px(8.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: BUTTON_COLOR.into(),
+                border_radius: BorderRadius::all(Val::Px(6.0)),
+                ..default()
+            },
+            action,
+        ))
+        .with_children(|button| {
+            button.spawn(TextBundle::from_section(label, text_style(24.0)));
+        });
+}
+
+fn text_style(font_size: f32) -> TextStyle {
+    TextStyle {
+        font_size,
+        color: Color::srgb(0.9, 0.9, 0.9),
+        ..default()
+    }
+}
+
+fn open_menu(mut cmds: Commands, mut draft: ResMut<SeedDraft>, world: Res<WorldSeed>) {
+    draft.0 = world.0.to_string();
+    cmds.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            background_color: BACKDROP.into(),
+            z_index: ZIndex::Global(10),
+            ..default()
+        },
+        MenuScreen,
+    ))
+    .with_children(|menu| {
+        menu.spawn(TextBundle::from_section("PAUSED", text_style(48.0)));
+        menu_button(menu, "Resume", MenuButton::Resume);
+        menu.spawn(TextBundle::from_section(
+            "seed, type digits to change",
+            text_style(18.0),
+        ));
+        menu.spawn(NodeBundle {
+            style: Style {
+                width: Val::Px(260.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            background_color: FIELD_COLOR.into(),
+            ..default()
+        })
+        .with_children(|field| {
+            field.spawn((TextBundle::from_section("", text_style(24.0)), DraftText));
+        });
+        menu_button(menu, "Random seed", MenuButton::RandomSeed);
+        menu_button(menu, "New universe", MenuButton::NewUniverse);
+        for bus in Bus::ALL {
+            volume_row(menu, bus);
+        }
+        stepper_row(
+            menu,
+            MenuButton::Shake(-SHAKE_STEP),
+            MenuButton::Shake(SHAKE_STEP),
+            ShakeText,
+        );
+    });
+}
+
+/// [-] name 80% [+]
+fn volume_row(parent: &mut ChildBuilder, bus: Bus) {
+    stepper_row(
+        parent,
+        MenuButton::Volume(bus, -VOLUME_STEP),
+        MenuButton::Volume(bus, VOLUME_STEP),
+        VolumeText(bus),
+    );
+}
+
+/// [-] label [+], the label is filled in by its own system
+fn stepper_row(parent: &mut ChildBuilder, less: MenuButton, more: MenuButton, label: impl Bundle) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Px(260.0),
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|row| {
+            sized_button(row, "-", less, 40.0);
+            row.spawn((TextBundle::from_section("", text_style(20.0)), label));
+            sized_button(row, "+", more, 40.0);
+        });
+}
+
+fn close_menu(mut cmds: Commands, q: Query<Entity, With<MenuScreen>>) {
+    for menu in q.iter() {
+        cmds.entity(menu).despawn_recursive();
+    }
+}
+
+fn type_seed(mut reader: EventReader<KeyboardInput>, mut draft: ResMut<SeedDraft>) {
+    for event in reader.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Character(c) => {
+                let digits = c.chars().filter(char::is_ascii_digit);
+                for digit in digits {
+                    if draft.0.len() < MAX_SEED_DIGITS {
+                        draft.0.push(digit);
+                    }
+                }
+            }
+            Key::Backspace => {
+                draft.0.pop();
+            }
+            _ => (),
+        }
+    }
+}
+
+fn menu_buttons(
+    mut q: Query<(&Interaction, &MenuButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut draft: ResMut<SeedDraft>,
+    mut world: ResMut<WorldSeed>,
+    mut next: ResMut<NextState<GameState>>,
+    mut volume: ResMut<VolumeSettings>,
+    mut shake: ResMut<ShakeSettings>,
+    mut sfx: EventWriter<PlaySfx>,
+) {
+    for (interaction, action, mut color) in q.iter_mut() {
+        *color = match interaction {
+            Interaction::Hovered => HOVERED_COLOR,
+            _ => BUTTON_COLOR,
+        }
+        .into();
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        sfx.send(PlaySfx::new(Sfx::Click));
+        match action {
+            MenuButton::Resume => next.set(GameState::Play),
+            MenuButton::RandomSeed => draft.0 = WorldSeed::default().0.to_string(),
+            MenuButton::NewUniverse => {
+                // an empty or oversized draft rolls a fresh seed
+                *world = draft.0.parse().map(WorldSeed).unwrap_or_default();
+                next.set(GameState::GameOver);
+            }
+            MenuButton::Volume(bus, delta) => volume.change(*bus, *delta),
+            MenuButton::Shake(delta) => shake.change(*delta),
+        }
+    }
+}
+
+fn show_draft(draft: Res<SeedDraft>, mut q: Query<&mut Text, With<DraftText>>) {
+    if !draft.is_changed() {
+        return;
+    }
+    for mut text in q.iter_mut() {
+        text.sections[0].value.clone_from(&draft.0);
+    }
+}
+
+fn show_volume(volume: Res<VolumeSettings>, mut q: Query<(Ref<VolumeText>, &mut Text)>) {
+    for (label, mut text) in q.iter_mut() {
+        if !volume.is_changed() && !label.is_added() {
+            continue;
+        }
+        let VolumeText(bus) = *label;
+        let percent = (volume.get(bus) * 100.0).round();
+        text.sections[0].value = format!("{} {percent}%", bus.name());
+    }
+}
+
+fn show_shake(shake: Res<ShakeSettings>, mut q: Query<(Ref<ShakeText>, &mut Text)>) {
+    for (label, mut text) in q.iter_mut() {
+        if !shake.is_changed() && !label.is_added() {
+            continue;
+        }
+        text.sections[0].value = if shake.scale > 0.0 {
+            format!("Shake {}%", (shake.scale * 100.0).round())
+        } else {
+            "Shake off".to_string()
+        };
+    }
+}
@@ -0,0 +1,187 @@
+//! background music, one looping layer per mood faded in and out by what is going on
+//!
+//! tracks are listed in the asset manifest, a mood without one stays silent
+
+use bevy::{audio::Volume, prelude::*};
+
+use crate::{
+    assets::MyAssets,
+    difficulty::Difficulty,
+    health::Health,
+    schedule::InitStages,
+    sentry::ThreatEvent,
+    ship::Player,
+    sound::{Bus, VolumeSettings},
+    state::GameState,
+};
+
+/// sentries further from the player than this do not count
+const COMBAT_RANGE: f32 = 300.0;
+/// intensity gained per second for each sentry engaging targets nearby
+const HEAT_UP: f32 = 1.0;
+/// intensity above this is combat
+const COMBAT_THRESHOLD: f32 = 1.0;
+/// intensity lost per second
+const CALM_DOWN: f32 = 0.15;
+/// share of full health below which the music turns grim
+const DANGER_HEALTH: f32 = 0.25;
+/// volume change per second while crossfading
+const FADE_SPEED: f32 = 0.5;
+/// seconds the game over music plays on, the game itself starts over right away
+const GAME_OVER_HOLD: f32 = 6.0;
+
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MusicDirector>()
+            .add_systems(OnEnter(GameState::GameOver), hold_game_over)
+            .add_systems(
+                OnExit(GameState::Loading),
+                start_layers.in_set(InitStages::Spawn),
+            )
+            .add_systems(Update, (measure_intensity, pick_mood, fade_layers).chain());
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mood {
+    #[default]
+    Calm,
+    Combat,
+    Danger,
+    Paused,
+    GameOver,
+}
+
+impl Mood {
+    const ALL: [Mood; 5] = [
+        Mood::Calm,
+        Mood::Combat,
+        Mood::Danger,
+        Mood::Paused,
+        Mood::GameOver,
+    ];
+
+    fn track<'a>(&self, assets: &'a MyAssets) -> &'a Handle<AudioSource> {
+        match self {
+            Mood::Calm => &assets.calm_music,
+            Mood::Combat => &assets.combat_music,
+            Mood::Danger => &assets.danger_music,
+            Mood::Paused => &assets.paused_music,
+            Mood::GameOver => &assets.game_over_music,
+        }
+    }
+}
+
+/// what the music is following
+#[derive(Resource, Default, Debug)]
+pub struct MusicDirector {
+    pub mood: Mood,
+    /// how much is happening around the player, raised by sentries fighting nearby
+    pub intensity: f32,
+    /// seconds of game over music left
+    pub game_over: f32,
+}
+
+/// a looping track, only audible while its mood is on
+#[derive(Component)]
+struct MusicLayer {
+    mood: Mood,
+    level: f32,
+}
+
+fn start_layers(mut cmds: Commands, assets: Res<MyAssets>) {
+    for mood in Mood::ALL {
+        let track = mood.track(&assets);
+        // not in the manifest
+        if *track == Handle::default() {
+            continue;
+        }
+        // all layers run from the start so they stay in step when fading across
+        let settings = PlaybackSettings::LOOP.with_volume(Volume::ZERO);
+        cmds.spawn((
+            AudioBundle {
+                source: track.clone(),
+                settings,
+            },
+            MusicLayer { mood, level: 0.0 },
+        ));
+    }
+}
+
+fn measure_intensity(
+    mut director: ResMut<MusicDirector>,
+    mut threats: EventReader<ThreatEvent>,
+    player_q: Query<&Transform, With<Player>>,
+    q: Query<&Transform>,
+    time: Res<Time>,
+) {
+    let player = player_q
+        .get_single()
+        .map(|transform| transform.translation.truncate());
+    let near = |entity: Entity| {
+        let Ok(player) = player else {
+            return false;
+        };
+        q.get(entity)
+            .is_ok_and(|transform| transform.translation.truncate().distance(player) < COMBAT_RANGE)
+    };
+    // a sentry reports every frame it has something in range
+    let engaged = threats.read().filter(|threat| near(threat.sentry)).count();
+    let heat = engaged as f32 * HEAT_UP - CALM_DOWN;
+    director.intensity = (director.intensity + heat * time.delta_seconds()).clamp(0.0, 3.0);
+}
+
+fn hold_game_over(mut director: ResMut<MusicDirector>) {
+    director.game_over = GAME_OVER_HOLD;
+}
+
+fn pick_mood(
+    mut director: ResMut<MusicDirector>,
+    state: Res<State<GameState>>,
+    player_q: Query<&Health, With<Player>>,
+    assets: Res<MyAssets>,
+    difficulty: Res<Difficulty>,
+    time: Res<Time<Real>>,
+) {
+    if director.game_over > 0.0 {
+        director.game_over = (director.game_over - time.delta_seconds()).max(0.0);
+    }
+    let health = player_q
+        .get_single()
+        .map(|health| health.life as f32 / difficulty.ship_health(&assets.tuning) as f32)
+        .unwrap_or(1.0);
+    let mood = match state.get() {
+        // the restart is immediate, long enough to hear it the mood stays on
+        _ if director.game_over > 0.0 => Mood::GameOver,
+        GameState::Loading | GameState::Paused | GameState::Map => Mood::Paused,
+        GameState::GameOver => Mood::GameOver,
+        GameState::Play if health < DANGER_HEALTH => Mood::Danger,
+        GameState::Play if director.intensity > COMBAT_THRESHOLD => Mood::Combat,
+        GameState::Play => Mood::Calm,
+    };
+    if director.mood != mood {
+        director.mood = mood;
+    }
+}
+
+fn fade_layers(
+    director: Res<MusicDirector>,
+    settings: Res<VolumeSettings>,
+    mut q: Query<(&mut MusicLayer, &AudioSink)>,
+    time: Res<Time<Real>>,
+) {
+    let step = FADE_SPEED * time.delta_seconds();
+    let gain = settings.gain(Bus::Music);
+    for (mut layer, sink) in q.iter_mut() {
+        let target = if layer.mood == director.mood {
+            1.0
+        } else {
+            0.0
+        };
+        let level = layer.level + (target - layer.level).clamp(-step, step);
+        layer.level = level;
+        sink.set_volume(level * gain);
+    }
+}
@@ -0,0 +1,325 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    astroids::Rock,
+    pool::{park_visual, Parked, Pool},
+    schedule::InGameSet,
+};
+
+/// upper bound of particles, live and parked, emitters go quiet instead of allocating more
+const MAX_PARTICLES: usize = 4096;
+
+pub struct ParticlePlugin;
+
+impl Plugin for ParticlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ParticleBurst>().add_systems(
+            Update,
+            (age_particles, emit_particles)
+                .chain()
+                .in_set(InGameSet::EntityUpdate),
+        );
+    }
+}
+
+/// value that can be blended between keyframes
+pub trait Tween: Copy {
+    fn tween(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Tween for f32 {
+    fn tween(&self, other: &Self, t: f32) -> Self {
+        self.lerp(*other, t)
+    }
+}
+
+impl Tween for Color {
+    fn tween(&self, other: &Self, t: f32) -> Self {
+        self.mix(other, t)
+    }
+}
+
+/// keyframes over normalized particle age, 0.0 is birth and 1.0 is death
+///
+/// a curve without keyframes is the default value all the way
+#[derive(Debug, Clone, Copy)]
+pub struct Curve<T: 'static>(pub &'static [(f32, T)]);
+
+impl<T: Tween + Default> Curve<T> {
+    pub fn sample(&self, t: f32) -> T {
+        let keys = self.0;
+        let Some(&(_, first)) = keys.first() else {
+            return T::default();
+        };
+        let mut prev = (0.0, first);
+        for &(at, value) in keys {
+            if t <= at {
+                let span = at - prev.0;
+                if span <= 0.0 {
+                    return value;
+                }
+                return prev.1.tween(&value, (t - prev.0) / span);
+            }
+            prev = (at, value);
+        }
+        prev.1
+    }
+}
+
+/// how particles are born and how they look over their lifetime
+#[derive(Debug, Clone, Copy)]
+pub struct ParticleConfig {
+    /// seconds, picked uniformly between min and max
+    pub lifetime: (f32, f32),
+    pub speed: (f32, f32),
+    /// half angle in radians around the emit direction
+    pub spread: f32,
+    /// share of the emitter velocity passed on to the particle
+    pub inherit: f32,
+    /// velocity lost per second
+    pub drag: f32,
+    pub color: Curve<Color>,
+    pub size: Curve<f32>,
+}
+
+impl ParticleConfig {
+    pub const EXHAUST: Self = Self {
+        lifetime: (0.25, 0.45),
+        speed: (25.0, 40.0),
+        spread: 0.25,
+        inherit: 1.0,
+        drag: 2.0,
+        color: Curve(&[
+            (0.0, Color::srgb(6.0, 4.0, 1.5)),
+            (0.3, Color::srgb(4.0, 1.2, 0.3)),
+            (1.0, Color::srgba(0.3, 0.3, 0.3, 0.0)),
+        ]),
+        size: Curve(&[(0.0, 1.5), (0.4, 2.5), (1.0, 3.5)]),
+    };
+
+    pub const PLASMA_IMPACT: Self = Self {
+        lifetime: (0.15, 0.3),
+        speed: (10.0, 30.0),
+        spread: std::f32::consts::PI,
+        inherit: 0.2,
+        drag: 4.0,
+        color: Curve(&[
+            (0.0, Color::srgb(7.5, 1.0, 7.5)),
+            (1.0, Color::srgba(2.0, 0.2, 2.0, 0.0)),
+        ]),
+        size: Curve(&[(0.0, 0.6), (1.0, 0.1)]),
+    };
+
+    pub const SHIP_EXPLOSION: Self = Self {
+        lifetime: (0.5, 1.1),
+        speed: (8.0, 35.0),
+        spread: std::f32::consts::PI,
+        inherit: 0.5,
+        drag: 1.5,
+        color: Curve(&[
+            (0.0, Color::srgb(8.0, 6.0, 3.0)),
+            (0.2, Color::srgb(6.0, 2.5, 0.6)),
+            (0.6, Color::srgb(1.5, 0.3, 0.1)),
+            (1.0, Color::srgba(0.2, 0.2, 0.2, 0.0)),
+        ]),
+        size: Curve(&[(0.0, 1.5), (0.3, 2.0), (1.0, 0.4)]),
+    };
+
+    const ROCK_DUST: Self = Self {
+        lifetime: (0.4, 0.8),
+        speed: (3.0, 12.0),
+        spread: std::f32::consts::PI,
+        inherit: 1.0,
+        drag: 1.0,
+        color: Curve(&[
+            (0.0, Color::srgb(0.6, 0.55, 0.5)),
+            (1.0, Color::srgba(0.3, 0.28, 0.25, 0.0)),
+        ]),
+        size: Curve(&[(0.0, 1.0), (1.0, 0.2)]),
+    };
+
+    pub fn rock_dust(kind: Rock) -> Self {
+        const ICE: Curve<Color> = Curve(&[
+            (0.0, Color::srgb(1.2, 1.8, 2.5)),
+            (1.0, Color::srgba(0.4, 0.6, 1.0, 0.0)),
+        ]);
+        const METAL: Curve<Color> = Curve(&[
+            (0.0, Color::srgb(3.0, 1.2, 0.4)),
+            (0.5, Color::srgb(1.0, 0.4, 0.1)),
+            (1.0, Color::srgba(0.2, 0.1, 0.1, 0.0)),
+        ]);
+        let color = match kind {
+            Rock::Stone => Self::ROCK_DUST.color,
+            Rock::Ice => ICE,
+            Rock::Metal => METAL,
+        };
+        Self {
+            color,
+            ..Self::ROCK_DUST
+        }
+    }
+
+    fn spawn_velocity(&self, rng: &mut impl Rng, direction: Vec2, inherited: Vec2) -> Vec2 {
+        let angle = rng.gen_range(-1.0..=1.0) * self.spread;
+        let speed = rng.gen_range(self.speed.0..=self.speed.1);
+        Vec2::from_angle(angle).rotate(direction) * speed + inherited * self.inherit
+    }
+
+    fn spawn_lifetime(&self, rng: &mut impl Rng) -> f32 {
+        rng.gen_range(self.lifetime.0..=self.lifetime.1)
+    }
+}
+
+/// continuous source of particles, emits along its local up axis
+#[derive(Component)]
+pub struct ParticleEmitter {
+    pub config: ParticleConfig,
+    /// particles per second while active
+    pub rate: f32,
+    pub active: bool,
+    /// velocity of whatever carries the emitter
+    pub velocity: Vec2,
+    pending: f32,
+}
+
+impl ParticleEmitter {
+    pub fn new(config: ParticleConfig, rate: f32) -> Self {
+        Self {
+            config,
+            rate,
+            active: false,
+            velocity: Vec2::ZERO,
+            pending: 0.0,
+        }
+    }
+}
+
+/// one-shot spray of particles in every direction
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ParticleBurst {
+    pub config: ParticleConfig,
+    pub count: usize,
+    pub position: Vec2,
+    pub velocity: Vec2,
+}
+
+#[derive(Component)]
+struct Particle {
+    config: ParticleConfig,
+    velocity: Vec2,
+    age: f32,
+    lifetime: f32,
+}
+
+struct Birth {
+    config: ParticleConfig,
+    position: Vec2,
+    velocity: Vec2,
+    lifetime: f32,
+}
+
+fn emit_particles(
+    mut cmds: Commands,
+    mut pool: Pool<Particle>,
+    mut bursts: EventReader<ParticleBurst>,
+    mut emitter_q: Query<(&mut ParticleEmitter, &GlobalTransform)>,
+    particle_q: Query<(), With<Particle>>,
+    parked_q: Query<(), (With<Particle>, With<Parked>)>,
+    time: Res<Time>,
+) {
+    let mut rng = rand::thread_rng();
+    let mut births = Vec::new();
+
+    for (mut emitter, global) in emitter_q.iter_mut() {
+        if !emitter.active {
+            emitter.pending = 0.0;
+            continue;
+        }
+        emitter.pending += emitter.rate * time.delta_seconds();
+        let direction = global.up().truncate().normalize_or_zero();
+        let position = global.translation().truncate();
+        while emitter.pending >= 1.0 {
+            emitter.pending -= 1.0;
+            let config = emitter.config;
+            births.push(Birth {
+                config,
+                position,
+                velocity: config.spawn_velocity(&mut rng, direction, emitter.velocity),
+                lifetime: config.spawn_lifetime(&mut rng),
+            });
+        }
+    }
+
+    for burst in bursts.read() {
+        for _ in 0..burst.count {
+            let direction = Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU));
+            births.push(Birth {
+                config: burst.config,
+                position: burst.position,
+                velocity: burst
+                    .config
+                    .spawn_velocity(&mut rng, direction, burst.velocity),
+                lifetime: burst.config.spawn_lifetime(&mut rng),
+            });
+        }
+    }
+
+    // parked particles are handed out first, new ones only while under the cap
+    let mut parked = parked_q.iter().count();
+    let mut total = particle_q.iter().count();
+    for birth in births {
+        if parked > 0 {
+            parked -= 1;
+        } else if total < MAX_PARTICLES {
+            total += 1;
+        } else {
+            break;
+        }
+        let particle = Particle {
+            config: birth.config,
+            velocity: birth.velocity,
+            age: 0.0,
+            lifetime: birth.lifetime,
+        };
+        let transform = Transform::from_translation(birth.position.extend(5.0));
+        pool.spawn(
+            &mut cmds,
+            (
+                SpriteBundle {
+                    transform,
+                    sprite: Sprite {
+                        color: birth.config.color.sample(0.0),
+                        custom_size: Some(Vec2::splat(birth.config.size.sample(0.0))),
+                        ..default()
+                    },
+                    ..default()
+                },
+                particle,
+                Name::new("Particle"),
+            ),
+        );
+    }
+}
+
+fn age_particles(
+    mut cmds: Commands,
+    mut q: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite), Without<Parked>>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_seconds();
+    for (entity, mut particle, mut transform, mut sprite) in q.iter_mut() {
+        particle.age += dt;
+        if particle.age >= particle.lifetime {
+            park_visual(&mut cmds.entity(entity));
+            continue;
+        }
+        let drag = (1.0 - particle.config.drag * dt).max(0.0);
+        particle.velocity *= drag;
+        transform.translation += (particle.velocity * dt).extend(0.0);
+
+        let t = particle.age / particle.lifetime;
+        sprite.color = particle.config.color.sample(t);
+        sprite.custom_size = Some(Vec2::splat(particle.config.size.sample(t)));
+    }
+}
@@ -0,0 +1,164 @@
+use avian2d::prelude::*;
+use bevy::{
+    ecs::entity::EntityHashMap,
+    prelude::*,
+    reflect::GetTypeRegistration,
+    scene::{DynamicEntity, SceneFilter},
+};
+
+use crate::{assets::MyAssets, health::Health};
+
+pub struct PersistPlugin;
+
+impl Plugin for PersistPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Persisted>()
+            .persist::<Persist>()
+            .persist::<Transform>()
+            .persist::<LinearVelocity>()
+            .persist::<AngularVelocity>()
+            .persist::<Health>();
+    }
+}
+
+/// entities with this are saved with their zone when it unloads
+#[derive(Component, Reflect, Default, Debug, Clone, Copy)]
+#[reflect(Component, Default)]
+pub struct Persist;
+
+/// rebuilds what a snapshot does not hold, like sprites, colliders and children
+///
+/// the saved components are written on top afterwards, so stage with defaults
+pub trait Restage {
+    fn restage(&self, entity: &mut EntityWorldMut, assets: &MyAssets);
+}
+
+type Restager = fn(&DynamicEntity, &mut World) -> Option<Entity>;
+
+/// which components go into a snapshot and how saved entities are brought back
+#[derive(Resource)]
+pub struct Persisted {
+    filter: SceneFilter,
+    restagers: Vec<Restager>,
+}
+
+impl Default for Persisted {
+    fn default() -> Self {
+        Self {
+            filter: SceneFilter::deny_all(),
+            restagers: Vec::new(),
+        }
+    }
+}
+
+pub trait PersistApp {
+    /// save this component with the entities of unloaded zones
+    fn persist<T: Component + GetTypeRegistration>(&mut self) -> &mut Self;
+    /// save this component and rebuild entities carrying it when their zone loads again
+    fn restage<T: Component + Restage + FromReflect + TypePath + GetTypeRegistration>(
+        &mut self,
+    ) -> &mut Self;
+}
+
+impl PersistApp for App {
+    fn persist<T: Component + GetTypeRegistration>(&mut self) -> &mut Self {
+        self.register_type::<T>();
+        let mut persisted = self
+            .world_mut()
+            .get_resource_or_insert_with(Persisted::default);
+        let filter = std::mem::take(&mut persisted.filter);
+        persisted.filter = filter.allow::<T>();
+        self
+    }
+
+    fn restage<T: Component + Restage + FromReflect + TypePath + GetTypeRegistration>(
+        &mut self,
+    ) -> &mut Self {
+        self.persist::<T>();
+        self.world_mut()
+            .get_resource_or_insert_with(Persisted::default)
+            .restagers
+            .push(restager::<T>);
+        self
+    }
+}
+
+/// saved entities with their persisted components
+#[derive(Default)]
+pub struct Snapshot(DynamicScene);
+
+impl std::fmt::Debug for Snapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Snapshot")
+            .field(&self.0.entities.len())
+            .finish()
+    }
+}
+
+impl Snapshot {
+    /// entities are only read, despawning them is up to the caller
+    pub fn take(world: &World, entities: &[Entity]) -> Self {
+        let filter = world.resource::<Persisted>().filter.clone();
+        let scene = DynamicSceneBuilder::from_world(world)
+            .with_filter(filter)
+            .extract_entities(entities.iter().copied())
+            .build();
+        Self(scene)
+    }
+
+    pub fn append(&mut self, other: Snapshot) {
+        self.0.entities.extend(other.0.entities);
+    }
+
+    /// every saved T
+    pub fn components<T: FromReflect + TypePath>(&self) -> impl Iterator<Item = T> + '_ {
+        self.0.entities.iter().filter_map(find::<T>)
+    }
+
+    /// every saved entity with both an A and a B
+    pub fn pairs<A, B>(&self) -> impl Iterator<Item = (A, B)> + '_
+    where
+        A: FromReflect + TypePath,
+        B: FromReflect + TypePath,
+    {
+        self.0
+            .entities
+            .iter()
+            .filter_map(|saved| Some((find::<A>(saved)?, find::<B>(saved)?)))
+    }
+
+    /// spawn the saved entities again, restaged and with their saved components on top
+    pub fn restore(self, world: &mut World) {
+        let restagers = world.resource::<Persisted>().restagers.clone();
+        let mut entity_map = EntityHashMap::default();
+        for saved in self.0.entities.iter() {
+            if let Some(entity) = restagers.iter().find_map(|restage| restage(saved, world)) {
+                entity_map.insert(saved.entity, entity);
+            }
+        }
+        if let Err(err) = self.0.write_to_world(world, &mut entity_map) {
+            error!("failed to restore snapshot: {err}");
+        }
+    }
+}
+
+fn find<T: FromReflect + TypePath>(saved: &DynamicEntity) -> Option<T> {
+    saved
+        .components
+        .iter()
+        .filter(|component| component.represents::<T>())
+        .find_map(|component| T::from_reflect(component.as_ref()))
+}
+
+fn restager<T: Component + Restage + FromReflect + TypePath>(
+    saved: &DynamicEntity,
+    world: &mut World,
+) -> Option<Entity> {
+    let component = find::<T>(saved)?;
+    let entity = world.resource_scope(|world, assets: Mut<MyAssets>| {
+        let mut entity = world.spawn_empty();
+        component.restage(&mut entity, &assets);
+        entity.id()
+    });
+    Some(entity)
+}
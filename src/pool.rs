@@ -0,0 +1,68 @@
+use avian2d::prelude::*;
+use bevy::{
+    ecs::{
+        entity::EntityHashSet,
+        system::{EntityCommands, SystemParam},
+    },
+    prelude::*,
+};
+
+use crate::{persist::Persist, spatial::Indexed};
+
+/// dead entities with this are parked for reuse instead of despawned
+#[derive(Component, Default, Debug, Clone, Copy)]
+pub struct Pooled;
+
+/// hidden, out of the physics and waiting for a [`Pool`] to hand it out again
+#[derive(Component, Default, Debug, Clone, Copy)]
+pub struct Parked;
+
+/// take a dead pooled entity out of play, it keeps its components until reused
+pub fn park(entity: &mut EntityCommands) {
+    park_visual(entity);
+    entity
+        .insert((
+            CollisionLayers::NONE,
+            RigidBody::Static,
+            LinearVelocity::ZERO,
+            AngularVelocity::ZERO,
+        ))
+        .remove::<(Persist, Indexed)>();
+}
+
+/// park a pooled entity that is only drawn, like a particle, it stays out of the physics
+pub fn park_visual(entity: &mut EntityCommands) {
+    entity.insert((Parked, Visibility::Hidden));
+}
+
+/// spawns entities carrying a T, reusing parked ones before allocating new
+#[derive(SystemParam)]
+pub struct Pool<'w, 's, T: Component> {
+    parked: Query<'w, 's, (Entity, Has<CollisionLayers>), (With<T>, With<Parked>)>,
+    /// handed out but still parked until commands are applied
+    taken: Local<'s, EntityHashSet>,
+}
+
+impl<T: Component> Pool<'_, '_, T> {
+    /// the bundle is inserted over whatever the reused entity held, so it should be complete
+    pub fn spawn(&mut self, cmds: &mut Commands, bundle: impl Bundle) -> Entity {
+        let parked = &self.parked;
+        self.taken.retain(|entity| parked.contains(*entity));
+        let reuse = parked
+            .iter()
+            .find(|(entity, _)| !self.taken.contains(entity))
+            .and_then(|(entity, physical)| Some((cmds.get_entity(entity)?, physical)));
+        let Some((mut entity, physical)) = reuse else {
+            return cmds.spawn((bundle, Pooled)).id();
+        };
+        entity.remove::<Parked>().insert(Visibility::Inherited);
+        // only parked bodies had their layers cleared
+        if physical {
+            entity.insert(CollisionLayers::default());
+        }
+        entity.insert(bundle);
+        let id = entity.id();
+        self.taken.insert(id);
+        id
+    }
+}
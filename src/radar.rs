@@ -0,0 +1,252 @@
+use bevy::prelude::*;
+
+use crate::{
+    pool::Parked,
+    schedule::InGameSet,
+    ship::Player,
+    zones::{Zone, ZoneState, Zones},
+};
+
+/// world distance covered from the center to the rim of the radar
+const RADAR_RANGE: f32 = Zone::SIZE;
+/// on screen size of the radar in pixels
+const RADAR_SIZE: f32 = 180.0;
+/// zones shown on each side of the player zone in the grid
+const GRID_REACH: i32 = 2;
+const CELL_SIZE: f32 = 16.0;
+
+const PANEL_COLOR: Color = Color::srgba(0.0, 0.1, 0.0, 0.6);
+const SPAWNED_COLOR: Color = Color::srgba(0.2, 0.8, 0.2, 0.6);
+const DESPAWNED_COLOR: Color = Color::srgba(0.5, 0.5, 0.5, 0.5);
+const UNKNOWN_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.3);
+
+pub struct RadarPlugin;
+
+impl Plugin for RadarPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Contacts>()
+            .configure_sets(
+                Update,
+                (RadarSet::Scan, RadarSet::Draw)
+                    .chain()
+                    .in_set(InGameSet::EntityUpdate),
+            )
+            .add_systems(Startup, spawn_radar)
+            .add_systems(Update, (draw_blips, draw_zone_grid).in_set(RadarSet::Draw));
+    }
+}
+
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
+pub enum RadarSet {
+    Scan,
+    Draw,
+}
+
+/// how something shows up on the radar
+#[derive(Debug, Clone, Copy)]
+pub struct Blip {
+    pub color: Color,
+    /// diameter in pixels
+    pub size: f32,
+}
+
+/// implement and register [`scan`] in [`RadarSet::Scan`] to have a component show on the radar
+pub trait RadarBlip {
+    fn blip(&self) -> Blip;
+}
+
+/// blips found this frame, relative to the player
+#[derive(Resource, Default)]
+pub struct Contacts(Vec<(Vec2, Blip)>);
+
+#[derive(Component)]
+struct RadarScreen;
+
+#[derive(Component)]
+struct BlipNode;
+
+#[derive(Component)]
+struct ZoneCell {
+    row: i32,
+    col: i32,
+}
+
+pub fn scan<T: Component + RadarBlip>(
+    q: Query<(&T, &Transform), Without<Parked>>,
+    player_q: Query<&Transform, With<Player>>,
+    mut contacts: ResMut<Contacts>,
+) {
+    let Ok(player) = player_q.get_single() else {
+        return;
+    };
+    let center = player.translation.truncate();
+    for (component, transform) in q.iter() {
+        let offset = transform.translation.truncate() - center;
+        if offset.length() > RADAR_RANGE {
+            continue;
+        }
+        contacts.0.push((offset, component.blip()));
+    }
+}
+
+fn spawn_radar(mut cmds: Commands) {
+    let panel = |width: f32, height: f32, radius: BorderRadius| NodeBundle {
+        style: Style {
+            width: Val::Px(width),
+            height: Val::Px(height),
+            ..default()
+        },
+        background_color: PANEL_COLOR.into(),
+        border_radius: radius,
+        ..default()
+    };
+
+    cmds.spawn(NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(8.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Start,
+            column_gap: Val::Px(8.0),
+            ..default()
+        },
+        ..default()
+    })
+    .with_children(|row| {
+        row.spawn((
+            panel(RADAR_SIZE, RADAR_SIZE, BorderRadius::MAX),
+            RadarScreen,
+        ))
+        .with_children(|radar| {
+            let ship_size = 6.0;
+            radar.spawn(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px((RADAR_SIZE - ship_size) / 2.0),
+                    top: Val::Px((RADAR_SIZE - ship_size) / 2.0),
+                    width: Val::Px(ship_size),
+                    height: Val::Px(ship_size),
+                    ..default()
+                },
+                background_color: Color::WHITE.into(),
+                ..default()
+            });
+        });
+
+        let side = (2 * GRID_REACH + 1) as f32 * CELL_SIZE;
+        row.spawn(panel(side, side, BorderRadius::all(Val::Px(4.0))))
+            .with_children(|grid| {
+                for row in -GRID_REACH..=GRID_REACH {
+                    for col in -GRID_REACH..=GRID_REACH {
+                        let border = if row == 0 && col == 0 { 2.0 } else { 0.0 };
+                        grid.spawn((
+                            NodeBundle {
+                                style: Style {
+                                    position_type: PositionType::Absolute,
+                                    left: Val::Px((col + GRID_REACH) as f32 * CELL_SIZE),
+                                    // rows count upwards in the world but downwards on screen
+                                    top: Val::Px((GRID_REACH - row) as f32 * CELL_SIZE),
+                                    width: Val::Px(CELL_SIZE - 1.0),
+                                    height: Val::Px(CELL_SIZE - 1.0),
+                                    border: UiRect::all(Val::Px(border)),
+                                    ..default()
+                                },
+                                border_color: Color::WHITE.into(),
+                                background_color: UNKNOWN_COLOR.into(),
+                                ..default()
+                            },
+                            ZoneCell { row, col },
+                        ));
+                    }
+                }
+            });
+    });
+}
+
+fn draw_blips(
+    mut cmds: Commands,
+    mut contacts: ResMut<Contacts>,
+    screen_q: Query<(Entity, Option<&Children>), With<RadarScreen>>,
+    mut blip_q: Query<(&mut Style, &mut BackgroundColor), With<BlipNode>>,
+) {
+    let Ok((screen, children)) = screen_q.get_single() else {
+        return;
+    };
+    let mut nodes = children
+        .into_iter()
+        .flatten()
+        .filter(|child| blip_q.contains(**child))
+        .copied()
+        .collect::<Vec<_>>()
+        .into_iter();
+
+    let half = RADAR_SIZE / 2.0;
+    for (offset, blip) in contacts.0.drain(..) {
+        let screen_pos = Vec2::new(offset.x, -offset.y) / RADAR_RANGE * half + half;
+        let left = Val::Px(screen_pos.x - blip.size / 2.0);
+        let top = Val::Px(screen_pos.y - blip.size / 2.0);
+        let size = Val::Px(blip.size);
+
+        match nodes.next() {
+            Some(node) => {
+                let Ok((mut style, mut color)) = blip_q.get_mut(node) else {
+                    continue;
+                };
+                style.display = Display::Flex;
+                style.left = left;
+                style.top = top;
+                style.width = size;
+                style.height = size;
+                *color = blip.color.into();
+            }
+            None => {
+                let node = cmds
+                    .spawn((
+                        NodeBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                left,
+                                top,
+                                width: size,
+                                height: size,
+                                ..default()
+                            },
+                            background_color: blip.color.into(),
+                            border_radius: BorderRadius::MAX,
+                            ..default()
+                        },
+                        BlipNode,
+                    ))
+                    .id();
+                cmds.entity(screen).add_child(node);
+            }
+        }
+    }
+    for node in nodes {
+        if let Ok((mut style, _)) = blip_q.get_mut(node) {
+            style.display = Display::None;
+        }
+    }
+}
+
+fn draw_zone_grid(
+    mut cell_q: Query<(&ZoneCell, &mut BackgroundColor)>,
+    player_q: Query<&Transform, With<Player>>,
+    zones: Res<Zones>,
+) {
+    let Ok(player) = player_q.get_single() else {
+        return;
+    };
+    let here: Zone = player.translation.truncate().into();
+    for (cell, mut color) in cell_q.iter_mut() {
+        let zone = Zone::new(here.row + cell.row, here.col + cell.col);
+        let seen = zones.visited.contains(&zone);
+        *color = match zones.state.get(&zone).filter(|_| seen) {
+            Some(ZoneState::Spawned) => SPAWNED_COLOR,
+            Some(ZoneState::Despawned(_)) => DESPAWNED_COLOR,
+            None => UNKNOWN_COLOR,
+        }
+        .into();
+    }
+}
@@ -13,7 +13,9 @@ pub enum InGameSet {
 
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
 pub enum InitStages {
+    /// at startup
     LoadAssets,
+    /// first entities, once loading is done
     Spawn,
 }
 
@@ -7,38 +7,97 @@ use crate::{
     assets::MyAssets,
     astroids::Astroid,
     collide_dmg::CollisionDamage,
+    console::{ahead_of_player, arg, ConsoleApp},
+    feedback::{blast_dead, Blast, DeathBlast},
     guns::{GunFireEvent, Plasma, PlasmaGun},
     health::{cry_dead, DeathCry, Health},
+    particles::ParticleConfig,
+    persist::{Persist, PersistApp, Restage},
+    radar::{scan, Blip, RadarBlip, RadarSet},
     schedule::{InGameSet, InitStages},
+    sound::Sfx,
+    spatial::{Indexed, SpatialIndex},
     stage::Stage,
+    state::GameState,
 };
 
 pub struct SentryPlugin;
 
-type Target = Astroid;
+/// what sentries shoot at
+pub type Target = Astroid;
 
 impl Plugin for SentryPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, init_dbg_sentry.in_set(InitStages::Spawn));
-        app.add_systems(Update, cry_dead::<Sentry>.in_set(InGameSet::Spawn));
+        app.add_systems(
+            OnExit(GameState::Loading),
+            init_dbg_sentry.in_set(InitStages::Spawn),
+        );
+        app.add_systems(
+            Update,
+            (cry_dead::<Sentry>, blast_dead::<Sentry>).in_set(InGameSet::Spawn),
+        );
+        app.add_systems(Update, scan::<Sentry>.in_set(RadarSet::Scan));
         app.add_systems(Update, detect_threat::<Target>);
         app.add_systems(Update, rotate_sentry);
         app.add_event::<ThreatEvent>();
         app.add_systems(Update, fire_ctrl);
+        app.restage::<Sentry>();
+        app.console_command(
+            "spawn sentry",
+            "[<level>] put a sentry ahead of the ship",
+            spawn_sentry,
+        );
     }
 }
 
-#[derive(Component)]
-struct Sentry;
+#[derive(Component, Reflect, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[reflect(Component)]
+pub struct Sentry {
+    /// each level adds health and fire rate, sentries far from the origin are higher
+    pub level: u8,
+}
+
+impl Sentry {
+    const RANGE: f32 = 100.0;
+    /// seconds between shots
+    const COOLDOWN: f32 = 0.2;
+    const HEALTH: i32 = 50;
+    pub const RADIUS: f32 = 2.5;
+    pub const MAX_LEVEL: u8 = 4;
+
+    pub fn new(level: u8) -> Self {
+        Self {
+            level: level.min(Self::MAX_LEVEL),
+        }
+    }
+
+    /// what a sentry needs besides its stage to find and shoot targets
+    pub fn equipment(&self) -> impl Bundle {
+        let cooldown = Self::COOLDOWN / (1.0 + self.level as f32 * 0.25);
+        (
+            Detector::<Target>::new(Self::RANGE),
+            PlasmaGun::new(cooldown),
+        )
+    }
+
+    fn health(&self) -> Health {
+        Health {
+            life: Self::HEALTH * (1 + self.level as i32),
+        }
+    }
+}
 
+/// looks for T within range through the [`SpatialIndex`]
 #[derive(Component)]
-struct Detector<T: Component> {
+pub struct Detector<T: Component> {
+    pub range: f32,
     phantom: PhantomData<T>,
 }
 
 impl<T: Component> Detector<T> {
-    fn new() -> Self {
+    fn new(range: f32) -> Self {
         Self {
+            range,
             phantom: PhantomData,
         }
     }
@@ -46,26 +105,35 @@ impl<T: Component> Detector<T> {
 
 fn init_dbg_sentry(mut cmds: Commands, assets: Res<MyAssets>) {
     let transform = Transform::from_xyz(0., 30., 0.);
-    let radius = 100.0;
-    let collider = Collider::circle(radius);
-    let detector = Detector::<Target>::new();
-    let gun = PlasmaGun::new(0.2);
-    cmds.spawn((Sentry.stage(&assets, transform), gun))
-        .with_children(|parrent| {
-            parrent.spawn((detector, collider, Sensor));
-        });
+    let sentry = Sentry::default();
+    cmds.spawn((sentry.stage(&assets, transform), sentry.equipment()));
+}
+
+fn spawn_sentry(world: &mut World, args: &[&str]) -> Result<String, String> {
+    let level = if args.is_empty() {
+        0
+    } else {
+        arg(args, 0, "level")?
+    };
+    let sentry = Sentry::new(level);
+    let transform = ahead_of_player(world, 30.0)?;
+    world.resource_scope(|world, assets: Mut<MyAssets>| {
+        world.spawn((sentry.stage(&assets, transform), sentry.equipment()));
+    });
+    Ok(format!("spawned {sentry:?}"))
 }
 
 #[derive(Clone, Copy, Debug)]
-struct Threat {
-    distance: f32,
-    radians: f32,
+pub struct Threat {
+    pub distance: f32,
+    /// direction relative to where the sentry is facing
+    pub radians: f32,
 }
 
 #[derive(Event)]
-struct ThreatEvent {
-    sentry: Entity,
-    threats: Box<[Threat]>,
+pub struct ThreatEvent {
+    pub sentry: Entity,
+    pub threats: Box<[Threat]>,
 }
 
 fn fire_ctrl(
@@ -115,15 +183,16 @@ fn rotate_sentry(
 }
 
 fn detect_threat<T: Component>(
-    sensor_q: Query<(&Parent, &CollidingEntities, &Position, &Rotation), With<Detector<T>>>,
+    sensor_q: Query<(Entity, &Detector<T>, &Position, &Rotation)>,
     threat_q: Query<&Transform, With<T>>,
     mut reporter: EventWriter<ThreatEvent>, // parrent_q: Query<&Transform>,
+    index: Res<SpatialIndex>,
 ) {
-    for (sentry, collisions, pos, &rot) in sensor_q.iter() {
-        let threats: Box<_> = collisions
-            .iter()
-            .filter_map(|entity| {
-                let Ok(threat_transform) = threat_q.get(*entity) else {
+    for (sentry, detector, pos, &rot) in sensor_q.iter() {
+        let threats: Box<_> = index
+            .within(**pos, detector.range)
+            .filter_map(|(entity, _)| {
+                let Ok(threat_transform) = threat_q.get(entity) else {
                     return None;
                 };
                 let linear_distance = threat_transform.translation.truncate() - **pos;
@@ -139,26 +208,38 @@ fn detect_threat<T: Component>(
         if threats.is_empty() {
             continue;
         }
-        let event = ThreatEvent {
-            sentry: **sentry,
-            threats,
-        };
+        let event = ThreatEvent { sentry, threats };
         reporter.send(event);
     }
 }
 
 impl DeathCry for Sentry {
-    fn cry(&self, assets: &MyAssets) -> AudioBundle {
-        let sound = AudioBundle {
-            source: assets.crack.clone(),
-            settings: PlaybackSettings::DESPAWN,
-        };
-        sound
+    fn cry(&self) -> Sfx {
+        Sfx::Crack
+    }
+}
+
+impl DeathBlast for Sentry {
+    fn blast(&self) -> Blast {
+        Blast {
+            particles: ParticleConfig::SHIP_EXPLOSION,
+            count: 60,
+            fireball: Some(12.0),
+        }
+    }
+}
+
+impl RadarBlip for Sentry {
+    fn blip(&self) -> Blip {
+        Blip {
+            color: Color::srgb(1.0, 0.1, 0.1),
+            size: 7.0,
+        }
     }
 }
 
 impl Stage for Sentry {
-    fn stage(self, assets: &Res<crate::assets::MyAssets>, transform: Transform) -> impl Bundle {
+    fn stage(self, assets: &MyAssets, transform: Transform) -> impl Bundle {
         let texture = assets.turret.clone();
         let model2d = SpriteBundle {
             transform,
@@ -170,14 +251,22 @@ impl Stage for Sentry {
             ..default()
         };
         (
-            Sentry,
+            self,
             Name::new("Sentry"),
             model2d,
             RigidBody::Dynamic,
             ColliderDensity(6.),
-            Collider::circle(2.5),
+            Collider::circle(Self::RADIUS),
             CollisionDamage(1),
-            Health { life: 50 },
+            self.health(),
+            Persist,
+            Indexed,
         )
     }
 }
+
+impl Restage for Sentry {
+    fn restage(&self, entity: &mut EntityWorldMut, assets: &MyAssets) {
+        entity.insert((self.stage(assets, Transform::default()), self.equipment()));
+    }
+}
@@ -9,32 +9,47 @@ use bevy::sprite::MaterialMesh2dBundle;
 
 use crate::assets::MyAssets;
 use crate::collide_dmg::CollisionDamage;
+use crate::console::{arg, player, ConsoleApp};
 use crate::despawn::Keep;
+use crate::difficulty::Difficulty;
+use crate::feedback::{blast_dead, Blast, DeathBlast};
 use crate::guns::{GunFireEvent, NinjaGun, PlasmaGun};
-use crate::health::Health;
-use crate::schedule::InGameSet;
+use crate::health::{Health, Invulnerable};
+use crate::launch::LaunchConfig;
+use crate::particles::{ParticleConfig, ParticleEmitter};
+use crate::schedule::{InGameSet, InitStages};
 use crate::state::GameState;
+use crate::zones::WorldSeed;
 
 // const START_TRANSLATION: Vec3 = Vec3::new(0., 0., -20.);
-const SHIP_SPEED: f32 = 25.0;
-const SHIP_ROTATION_SPEED: f32 = 2.5;
 const SHIP_ROLL_SPEED: f32 = 2.5;
-const SHIP_HEALTH: i32 = 1000000;
 const SHIP_COLLISION_DAMAGE: i32 = 30;
+/// seconds between plasma shots
+const SHIP_GUN_COOLDOWN: f32 = 0.15;
 
 const FORWARD_OFFSET: f32 = 8.5;
 pub struct ShipPlug;
 
 impl Plugin for ShipPlug {
     fn build(&self, app: &mut App) {
-        app.add_systems(PostStartup, spawn_spaceship);
+        app.add_systems(
+            OnExit(GameState::Loading),
+            spawn_spaceship.in_set(InitStages::Spawn),
+        );
         app.add_systems(OnExit(GameState::GameOver), spawn_spaceship);
         app.add_systems(Update, ship_weapon_ctrl.in_set(InGameSet::UI));
+        app.add_systems(Update, blast_dead::<SpaceShip>.in_set(InGameSet::Spawn));
         app.add_systems(
             Update,
             (ship_movement_ctrl, shield_ctrl).in_set(InGameSet::UI),
         )
-        .add_systems(Update, end_player);
+        .add_systems(Update, end_player)
+        .console_command("god", "toggle taking damage", god)
+        .console_command(
+            "give",
+            "<health|plasma|hook> refill or re-equip the ship",
+            give,
+        );
     }
 }
 
@@ -44,6 +59,16 @@ pub struct SpaceShip;
 #[derive(Component)]
 pub struct Player;
 
+impl DeathBlast for SpaceShip {
+    fn blast(&self) -> Blast {
+        Blast {
+            particles: ParticleConfig::SHIP_EXPLOSION,
+            count: 150,
+            fireball: Some(25.0),
+        }
+    }
+}
+
 #[derive(Component, Debug)]
 struct Shield;
 
@@ -93,24 +118,27 @@ fn shield_ctrl(
 
 // type ShipQuery = Query<(&mut Transform, &mut Velocity), With<SpaceShip>>;
 fn ship_movement_ctrl(
-    mut q: Query<(&mut Transform, &mut LinearVelocity), With<SpaceShip>>,
+    mut q: Query<(&mut Transform, &mut LinearVelocity, Option<&Children>), With<SpaceShip>>,
+    mut exhaust_q: Query<&mut ParticleEmitter>,
     key_input: Res<ButtonInput<KeyCode>>,
     time: Res<Time>,
+    assets: Res<MyAssets>,
 ) {
-    let Ok((mut transform, mut velocity)) = q.get_single_mut() else {
+    let Ok((mut transform, mut velocity, children)) = q.get_single_mut() else {
         return;
     };
+    let tuning = &assets.tuning;
 
     let mut movement = 0.0;
     if key_input.pressed(KeyCode::ArrowDown) {
-        movement = -SHIP_SPEED;
+        movement = -tuning.ship_speed;
     } else if key_input.pressed(KeyCode::ArrowUp) {
-        movement = SHIP_SPEED;
+        movement = tuning.ship_speed;
     }
 
     let mut rotation = 0.0;
     if key_input.pressed(KeyCode::ArrowLeft) {
-        rotation = SHIP_ROTATION_SPEED;
+        rotation = tuning.ship_rotation_speed;
     } else if key_input.pressed(KeyCode::ArrowRight) {
         rotation = -SHIP_ROLL_SPEED;
     }
@@ -127,6 +155,13 @@ fn ship_movement_ctrl(
     transform.rotate_local_y(-roll * dt);
 
     velocity.0 += -(transform.up()).truncate() * movement * dt;
+
+    for child in children.into_iter().flatten() {
+        if let Ok(mut exhaust) = exhaust_q.get_mut(*child) {
+            exhaust.active = movement > 0.0;
+            exhaust.velocity = velocity.0;
+        }
+    }
 }
 
 fn spawn_spaceship(
@@ -134,6 +169,9 @@ fn spawn_spaceship(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     assets: Res<MyAssets>,
+    difficulty: Res<Difficulty>,
+    launch: Res<LaunchConfig>,
+    world: Res<WorldSeed>,
 ) {
     // transform.rotate_local_x(90.0f32.to_radians());
     // transform.rotate_x(90.0f32.to_radians());
@@ -143,7 +181,8 @@ fn spawn_spaceship(
     });
     let mut scale = [1., 1.5, 1.0_f32].into();
     scale *= 5.0;
-    let mut transform = Transform::from_xyz(0., 10., 0.).with_scale(scale);
+    let start = launch.start(*world).extend(0.0) + Vec3::new(0., 10., 0.);
+    let mut transform = Transform::from_translation(start).with_scale(scale);
     transform.rotate_z(PI);
     let model2d = SpriteBundle {
         transform,
@@ -170,17 +209,27 @@ fn spawn_spaceship(
         // derp,
         Player,
         SpaceShip,
-        PlasmaGun::new(0.15),
+        PlasmaGun::new(SHIP_GUN_COOLDOWN),
         NinjaGun::default(),
         Keep,
         Health {
-            life: SHIP_HEALTH,
+            life: difficulty.ship_health(&assets.tuning),
             ..Default::default()
         },
         CollisionDamage(SHIP_COLLISION_DAMAGE),
         Name::new("PlayerShip"),
     );
-    let _entity1 = cmds.spawn(ship).id();
+    let exhaust = (
+        ParticleEmitter::new(ParticleConfig::EXHAUST, 60.0),
+        TransformBundle::from_transform(Transform::from_xyz(0., 1.1, -0.1)),
+    );
+    let mut entity1 = cmds.spawn(ship);
+    entity1.with_children(|parent| {
+        parent.spawn(exhaust);
+    });
+    if launch.god {
+        entity1.insert(Invulnerable);
+    }
 
     // let entity2 = cmds
     //     .spawn((camera, Keep, RigidBody::Dynamic, Collider::circle(0.1)))
@@ -191,6 +240,34 @@ fn spawn_spaceship(
 
 // struct WeponState(f32);
 
+fn god(world: &mut World, _: &[&str]) -> Result<String, String> {
+    let ship = player(world)?;
+    let mut ship = world.entity_mut(ship);
+    if ship.contains::<Invulnerable>() {
+        ship.remove::<Invulnerable>();
+        Ok("god mode off".into())
+    } else {
+        ship.insert(Invulnerable);
+        Ok("god mode on".into())
+    }
+}
+
+fn give(world: &mut World, args: &[&str]) -> Result<String, String> {
+    let item: String = arg(args, 0, "item")?;
+    let life = world
+        .resource::<Difficulty>()
+        .ship_health(&world.resource::<MyAssets>().tuning);
+    let ship = player(world)?;
+    let mut ship = world.entity_mut(ship);
+    match item.as_str() {
+        "health" => ship.insert(Health { life }),
+        "plasma" => ship.insert(PlasmaGun::new(SHIP_GUN_COOLDOWN)),
+        "hook" => ship.insert(NinjaGun::default()),
+        _ => return Err(format!("nothing called '{item}' to give")),
+    };
+    Ok(format!("gave {item}"))
+}
+
 fn end_player(mut next: ResMut<NextState<GameState>>, q: Query<(), With<SpaceShip>>) {
     if q.get_single().is_err() {
         next.set(GameState::GameOver)
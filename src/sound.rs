@@ -0,0 +1,299 @@
+use bevy::{
+    audio::{PlaybackMode, SpatialScale, Volume},
+    prelude::*,
+    utils::HashMap,
+};
+
+use crate::{assets::MyAssets, launch::LaunchConfig};
+
+/// world units to audio units, a sound this close to the listener plays at full volume
+const SPATIAL_SCALE: f32 = 1.0 / 40.0;
+/// sounds further away are dropped before they take a voice
+const HEARING_RANGE: f32 = 1000.0;
+/// distance between the ears of the listener in world units
+const EAR_GAP: f32 = 20.0;
+
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VolumeSettings>()
+            .register_type::<VolumeSettings>()
+            .add_event::<PlaySfx>()
+            .add_systems(Startup, apply_launch_mute)
+            .add_systems(Update, (play_sfx, apply_volume).chain());
+    }
+}
+
+/// volume groups the player can turn up and down, master scales all the others
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum Bus {
+    Master,
+    Sfx,
+    Ui,
+    Music,
+}
+
+impl Bus {
+    pub const ALL: [Bus; 4] = [Bus::Master, Bus::Sfx, Bus::Ui, Bus::Music];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Bus::Master => "Master",
+            Bus::Sfx => "Effects",
+            Bus::Ui => "Interface",
+            Bus::Music => "Music",
+        }
+    }
+}
+
+/// user volume per bus, 0 is silent and 1 is full
+#[derive(Resource, Reflect, Debug, Clone, Copy)]
+#[reflect(Resource)]
+pub struct VolumeSettings {
+    pub master: f32,
+    pub sfx: f32,
+    pub ui: f32,
+    pub music: f32,
+}
+
+impl Default for VolumeSettings {
+    fn default() -> Self {
+        Self {
+            master: 0.8,
+            sfx: 1.0,
+            ui: 1.0,
+            music: 0.6,
+        }
+    }
+}
+
+impl VolumeSettings {
+    pub fn get(&self, bus: Bus) -> f32 {
+        match bus {
+            Bus::Master => self.master,
+            Bus::Sfx => self.sfx,
+            Bus::Ui => self.ui,
+            Bus::Music => self.music,
+        }
+    }
+
+    /// nudge a bus, kept within 0..1
+    pub fn change(&mut self, bus: Bus, delta: f32) {
+        let volume = match bus {
+            Bus::Master => &mut self.master,
+            Bus::Sfx => &mut self.sfx,
+            Bus::Ui => &mut self.ui,
+            Bus::Music => &mut self.music,
+        };
+        *volume = (*volume + delta).clamp(0.0, 1.0);
+    }
+
+    /// what a sound on this bus is scaled by
+    pub fn gain(&self, bus: Bus) -> f32 {
+        match bus {
+            Bus::Master => self.master,
+            bus => self.master * self.get(bus),
+        }
+    }
+}
+
+fn apply_launch_mute(launch: Res<LaunchConfig>, mut settings: ResMut<VolumeSettings>) {
+    if launch.mute {
+        settings.master = 0.0;
+    }
+}
+
+/// every sound effect in the game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sfx {
+    Laser,
+    HookLaunch,
+    HookImpact,
+    Crack,
+    Pop,
+    Click,
+}
+
+/// how a sound effect is played
+#[derive(Debug, Clone, Copy)]
+pub struct SfxParams {
+    pub bus: Bus,
+    pub volume: f32,
+    pub speed: f32,
+    /// most copies playing at once, more are dropped
+    pub voices: usize,
+    /// seconds before the sound can start again
+    pub cooldown: f32,
+}
+
+impl Sfx {
+    pub fn params(&self) -> SfxParams {
+        match self {
+            Sfx::Laser => SfxParams {
+                bus: Bus::Sfx,
+                volume: 0.3,
+                speed: 1.5,
+                voices: 4,
+                cooldown: 0.05,
+            },
+            Sfx::HookLaunch => SfxParams {
+                bus: Bus::Sfx,
+                volume: 0.3,
+                speed: 1.5,
+                voices: 2,
+                cooldown: 0.1,
+            },
+            Sfx::HookImpact => SfxParams {
+                bus: Bus::Sfx,
+                volume: 0.5,
+                speed: 1.5,
+                voices: 2,
+                cooldown: 0.1,
+            },
+            Sfx::Crack => SfxParams {
+                bus: Bus::Sfx,
+                volume: 1.0,
+                speed: 1.0,
+                voices: 6,
+                cooldown: 0.03,
+            },
+            Sfx::Pop => SfxParams {
+                bus: Bus::Sfx,
+                volume: 1.0,
+                speed: 1.0,
+                voices: 6,
+                cooldown: 0.02,
+            },
+            Sfx::Click => SfxParams {
+                bus: Bus::Ui,
+                volume: 0.5,
+                speed: 2.0,
+                voices: 2,
+                cooldown: 0.05,
+            },
+        }
+    }
+
+    fn source(&self, assets: &MyAssets) -> Handle<AudioSource> {
+        match self {
+            Sfx::Laser => assets.laser_sound.clone(),
+            Sfx::HookLaunch => assets.muffled_laser.clone(),
+            Sfx::HookImpact => assets.doing.clone(),
+            Sfx::Crack => assets.crack.clone(),
+            Sfx::Pop | Sfx::Click => assets.pop.clone(),
+        }
+    }
+}
+
+/// send instead of spawning audio, limits and volume settings are applied on the way
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PlaySfx {
+    pub sfx: Sfx,
+    /// where in the world it happened, heard relative to the [`SpatialListener`]
+    pub at: Option<Vec2>,
+}
+
+impl PlaySfx {
+    /// same in both ears whatever happens, for interface sounds
+    pub fn new(sfx: Sfx) -> Self {
+        Self { sfx, at: None }
+    }
+
+    pub fn at(sfx: Sfx, position: Vec2) -> Self {
+        Self {
+            sfx,
+            at: Some(position),
+        }
+    }
+}
+
+/// ears for the camera, spatial sounds pan and fade relative to it
+pub fn listener() -> SpatialListener {
+    SpatialListener::new(EAR_GAP)
+}
+
+/// a playing sound effect
+#[derive(Component)]
+struct Voice(Sfx);
+
+#[allow(clippy::too_many_arguments)]
+fn play_sfx(
+    mut cmds: Commands,
+    mut reader: EventReader<PlaySfx>,
+    voice_q: Query<&Voice>,
+    assets: Res<MyAssets>,
+    settings: Res<VolumeSettings>,
+    time: Res<Time<Real>>,
+    listener_q: Query<&GlobalTransform, With<SpatialListener>>,
+    mut last_played: Local<HashMap<Sfx, f32>>,
+) {
+    if reader.is_empty() {
+        return;
+    }
+    let mut playing: HashMap<Sfx, usize> = HashMap::new();
+    for Voice(sfx) in voice_q.iter() {
+        *playing.entry(*sfx).or_default() += 1;
+    }
+    let now = time.elapsed_seconds();
+    let listener = listener_q
+        .get_single()
+        .map(|transform| transform.translation().truncate())
+        .ok();
+    for PlaySfx { sfx, at } in reader.read() {
+        let out_of_range = at
+            .zip(listener)
+            .is_some_and(|(at, listener)| at.distance(listener) > HEARING_RANGE);
+        if out_of_range {
+            continue;
+        }
+        let params = sfx.params();
+        let cooling = last_played
+            .get(sfx)
+            .is_some_and(|last| now - last < params.cooldown);
+        let voices = playing.entry(*sfx).or_default();
+        if cooling || *voices >= params.voices {
+            continue;
+        }
+        *voices += 1;
+        last_played.insert(*sfx, now);
+        let settings = PlaybackSettings {
+            mode: PlaybackMode::Despawn,
+            speed: params.speed,
+            volume: Volume::new(params.volume * settings.gain(params.bus)),
+            spatial: at.is_some(),
+            spatial_scale: Some(SpatialScale::new_2d(SPATIAL_SCALE)),
+            ..default()
+        };
+        let position = at.unwrap_or_default().extend(0.0);
+        cmds.spawn((
+            AudioBundle {
+                source: sfx.source(&assets),
+                settings,
+            },
+            TransformBundle::from_transform(Transform::from_translation(position)),
+            Voice(*sfx),
+        ));
+    }
+}
+
+/// sounds already playing follow the settings too
+fn apply_volume(
+    settings: Res<VolumeSettings>,
+    q: Query<(&Voice, &AudioSink)>,
+    spatial_q: Query<(&Voice, &SpatialAudioSink)>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    let volume = |Voice(sfx): &Voice| {
+        let params = sfx.params();
+        params.volume * settings.gain(params.bus)
+    };
+    for (voice, sink) in q.iter() {
+        sink.set_volume(volume(voice));
+    }
+    for (voice, sink) in spatial_q.iter() {
+        sink.set_volume(volume(voice));
+    }
+}
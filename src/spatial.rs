@@ -0,0 +1,204 @@
+use bevy::{ecs::entity::EntityHashMap, prelude::*, utils::HashMap};
+
+use crate::{schedule::InGameSet, zones::Zone};
+
+/// cells along one side of a zone
+const CELLS_PER_ZONE: i32 = 8;
+const CELL_SIZE: f32 = Zone::SIZE * 2.0 / CELLS_PER_ZONE as f32;
+
+pub struct SpatialPlugin;
+
+impl Plugin for SpatialPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpatialIndex>()
+            .add_systems(Update, update_index.before(InGameSet::Despawn));
+    }
+}
+
+/// entities with this are tracked by the [`SpatialIndex`]
+#[derive(Component, Default, Debug, Clone, Copy)]
+pub struct Indexed;
+
+/// uniform grid over the world, cells line up with zone borders
+///
+/// positions are as of the start of the frame, entities may have been despawned since
+#[derive(Resource, Default)]
+pub struct SpatialIndex {
+    cells: HashMap<IVec2, Vec<Entity>>,
+    entries: EntityHashMap<(IVec2, Vec2)>,
+}
+
+impl SpatialIndex {
+    fn cell(position: Vec2) -> IVec2 {
+        ((position + Zone::SIZE) / CELL_SIZE).floor().as_ivec2()
+    }
+
+    /// distance from a point to the far corner of a cell
+    fn cell_reach(cell: IVec2, point: Vec2) -> f32 {
+        let min = cell.as_vec2() * CELL_SIZE - Zone::SIZE;
+        let max = min + CELL_SIZE;
+        let far_corner = Vec2::select(point.cmplt((min + max) / 2.0), max, min);
+        far_corner.distance(point)
+    }
+
+    pub fn position(&self, entity: Entity) -> Option<Vec2> {
+        self.entries.get(&entity).map(|(_, position)| *position)
+    }
+
+    fn insert(&mut self, entity: Entity, position: Vec2) {
+        let cell = Self::cell(position);
+        match self.entries.insert(entity, (cell, position)) {
+            Some((old, _)) if old == cell => return,
+            Some((old, _)) => self.remove_from_cell(entity, old),
+            None => (),
+        }
+        self.cells.entry(cell).or_default().push(entity);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        if let Some((cell, _)) = self.entries.remove(&entity) {
+            self.remove_from_cell(entity, cell);
+        }
+    }
+
+    fn remove_from_cell(&mut self, entity: Entity, cell: IVec2) {
+        let Some(entities) = self.cells.get_mut(&cell) else {
+            return;
+        };
+        if let Some(i) = entities.iter().position(|e| *e == entity) {
+            entities.swap_remove(i);
+        }
+        if entities.is_empty() {
+            self.cells.remove(&cell);
+        }
+    }
+
+    fn cell_entries<'a>(
+        &'a self,
+        entities: &'a [Entity],
+    ) -> impl Iterator<Item = (Entity, Vec2)> + 'a {
+        entities
+            .iter()
+            .filter_map(|entity| Some((*entity, self.position(*entity)?)))
+    }
+
+    /// everything within radius of center
+    pub fn within(&self, center: Vec2, radius: f32) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        let min = Self::cell(center - radius);
+        let max = Self::cell(center + radius);
+        (min.y..=max.y)
+            .flat_map(move |y| (min.x..=max.x).map(move |x| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flat_map(|entities| self.cell_entries(entities))
+            .filter(move |(_, position)| position.distance(center) <= radius)
+    }
+
+    /// everything further than radius from center, cells well inside are skipped whole
+    pub fn outside(&self, center: Vec2, radius: f32) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        self.cells
+            .iter()
+            .filter(move |(cell, _)| Self::cell_reach(**cell, center) > radius)
+            .flat_map(|(_, entities)| self.cell_entries(entities))
+            .filter(move |(_, position)| position.distance(center) > radius)
+    }
+
+    /// everything inside the borders of a zone
+    pub fn in_zone(&self, zone: Zone) -> impl Iterator<Item = (Entity, Vec2)> + '_ {
+        let corner = IVec2::new(zone.col, zone.row) * CELLS_PER_ZONE;
+        (0..CELLS_PER_ZONE)
+            .flat_map(move |y| (0..CELLS_PER_ZONE).map(move |x| corner + IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flat_map(|entities| self.cell_entries(entities))
+    }
+}
+
+fn update_index(
+    mut index: ResMut<SpatialIndex>,
+    q: Query<(Entity, &Transform), (With<Indexed>, Changed<Transform>)>,
+    mut removed: RemovedComponents<Indexed>,
+) {
+    for entity in removed.read() {
+        index.remove(entity);
+    }
+    for (entity, transform) in q.iter() {
+        index.insert(entity, transform.translation.truncate());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(positions: &[Vec2]) -> SpatialIndex {
+        let mut index = SpatialIndex::default();
+        for (i, position) in positions.iter().enumerate() {
+            index.insert(Entity::from_raw(i as u32), *position);
+        }
+        index
+    }
+
+    fn found(hits: impl Iterator<Item = (Entity, Vec2)>) -> Vec<u32> {
+        let mut found: Vec<u32> = hits.map(|(entity, _)| entity.index()).collect();
+        found.sort();
+        found
+    }
+
+    #[test]
+    fn within_reaches_over_cell_borders() {
+        let border = CELL_SIZE - Zone::SIZE;
+        let index = index(&[
+            Vec2::new(border - 3.0, 0.0),
+            Vec2::new(border + 3.0, 0.0),
+            Vec2::new(border - 9.0, 0.0),
+        ]);
+        assert_ne!(
+            SpatialIndex::cell(Vec2::new(border - 3.0, 0.0)),
+            SpatialIndex::cell(Vec2::new(border + 3.0, 0.0))
+        );
+        assert_eq!(found(index.within(Vec2::new(border, 0.0), 5.0)), [0, 1]);
+    }
+
+    #[test]
+    fn within_works_at_negative_coordinates() {
+        let index = index(&[
+            Vec2::new(-950.0, -1210.0),
+            Vec2::new(-900.0, -1200.0),
+            Vec2::new(950.0, 1210.0),
+        ]);
+        assert_eq!(found(index.within(Vec2::new(-950.0, -1200.0), 15.0)), [0]);
+        assert_eq!(
+            found(index.within(Vec2::new(-925.0, -1205.0), 30.0)),
+            [0, 1]
+        );
+    }
+
+    #[test]
+    fn in_zone_keeps_to_the_zone_borders() {
+        let zone = Zone::new(-1, 2);
+        let center = zone.center();
+        let index = index(&[
+            center + Vec2::new(Zone::SIZE - 1.0, -Zone::SIZE + 1.0),
+            center + Vec2::new(-Zone::SIZE + 1.0, Zone::SIZE - 1.0),
+            center + Vec2::new(Zone::SIZE + 1.0, 0.0),
+            center + Vec2::new(0.0, -Zone::SIZE - 1.0),
+            Vec2::ZERO,
+        ]);
+        assert_eq!(found(index.in_zone(zone)), [0, 1]);
+    }
+
+    #[test]
+    fn outside_only_skips_cells_inside_the_radius() {
+        // the cell from the origin up to one cell size, its far corner is the diagonal
+        let cell = SpatialIndex::cell(Vec2::splat(1.0));
+        let diagonal = CELL_SIZE * 2.0_f32.sqrt();
+        assert!((SpatialIndex::cell_reach(cell, Vec2::ZERO) - diagonal).abs() < 1e-3);
+        assert!((SpatialIndex::cell_reach(cell, Vec2::splat(CELL_SIZE)) - diagonal).abs() < 1e-3);
+
+        let corner = Vec2::splat(CELL_SIZE - 1.0);
+        let index = index(&[Vec2::splat(10.0), corner, Vec2::new(-200.0, 0.0)]);
+        let radius = corner.length() - 1.0;
+        assert!(radius < diagonal);
+        assert_eq!(found(index.outside(Vec2::ZERO, radius)), [1, 2]);
+        assert_eq!(found(index.outside(Vec2::ZERO, diagonal + 1.0)), [2]);
+    }
+}
@@ -5,15 +5,15 @@ use crate::assets::MyAssets;
 
 pub trait Extra {
     type Extras: Bundle + Sized;
-    fn extra(&self) -> Self::Extras;
+    fn extra(&self, assets: &MyAssets) -> Self::Extras;
 }
 
 pub trait Stage {
-    fn stage(self, assets: &Res<MyAssets>, transform: Transform) -> impl Bundle;
+    fn stage(self, assets: &MyAssets, transform: Transform) -> impl Bundle;
 }
 pub trait IntoMovingBundle {
     type Extras: Bundle + Sized;
-    fn bundle(self, assets: &Res<MyAssets>, transform: Transform, velocity: Vec2) -> impl Bundle;
+    fn bundle(self, assets: &MyAssets, transform: Transform, velocity: Vec2) -> impl Bundle;
 }
 
 impl<C, T> IntoMovingBundle for T
@@ -23,9 +23,9 @@ where
 {
     type Extras = C;
 
-    fn bundle(self, assets: &Res<MyAssets>, transform: Transform, velocity: Vec2) -> impl Bundle {
+    fn bundle(self, assets: &MyAssets, transform: Transform, velocity: Vec2) -> impl Bundle {
         (
-            self.extra(),
+            self.extra(assets),
             self.stage(assets, transform),
             LinearVelocity(velocity),
             self,
@@ -1,10 +1,15 @@
+use avian2d::prelude::*;
 use bevy::prelude::*;
 
 #[derive(States, Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum GameState {
+    /// waiting for the asset manifest, nothing is spawned yet
     #[default]
+    Loading,
     Play,
     Paused,
+    /// world map, the game is paused behind it
+    Map,
     GameOver,
 }
 
@@ -14,7 +19,11 @@ impl Plugin for StatePlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<GameState>()
             .add_systems(Update, restart_game.run_if(in_state(GameState::GameOver)))
-            .add_systems(Update, toggle_game_state);
+            .add_systems(Update, toggle_game_state)
+            .add_systems(OnEnter(GameState::Paused), pause_time)
+            .add_systems(OnExit(GameState::Paused), resume_time)
+            .add_systems(OnEnter(GameState::Map), pause_time)
+            .add_systems(OnExit(GameState::Map), resume_time);
     }
 }
 
@@ -27,9 +36,28 @@ fn toggle_game_state(
         match state.get() {
             GameState::Play => next_state.set(GameState::Paused),
             GameState::Paused => next_state.set(GameState::Play),
-            GameState::GameOver => todo!(),
+            GameState::Map => next_state.set(GameState::Play),
+            GameState::GameOver | GameState::Loading => (),
         }
     }
+    if key_input.just_pressed(KeyCode::KeyM) {
+        match state.get() {
+            GameState::Play => next_state.set(GameState::Map),
+            GameState::Map => next_state.set(GameState::Play),
+            _ => (),
+        }
+    }
+}
+
+/// physics and every timer stop, nothing can hurt the ship while a screen is open
+fn pause_time(mut time: ResMut<Time<Virtual>>, mut physics: ResMut<Time<Physics>>) {
+    time.pause();
+    physics.pause();
+}
+
+fn resume_time(mut time: ResMut<Time<Virtual>>, mut physics: ResMut<Time<Physics>>) {
+    time.unpause();
+    physics.unpause();
 }
 
 fn restart_game(mut next_state: ResMut<NextState<GameState>>) {
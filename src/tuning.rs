@@ -0,0 +1,198 @@
+use bevy::{prelude::*, reflect::Struct};
+use serde::Deserialize;
+
+use crate::{
+    assets::{MyAssets, RonLoader},
+    console::{arg, ConsoleApp},
+};
+
+const TUNING: &str = "game.tuning.ron";
+/// health, sizes and distances, zero breaks the game where the speeds only stop things
+const NOT_ZERO: [&str; 7] = [
+    "ship_speed",
+    "ship_health",
+    "astroid_radius",
+    "astroid_life",
+    "zone_despawn_distance",
+    "max_distance",
+    "hook_length",
+];
+
+pub struct TuningPlugin;
+
+impl Plugin for TuningPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Tuning>()
+            .register_type::<Tuning>()
+            .register_asset_loader(RonLoader::<Tuning>::new(&["tuning.ron"]))
+            .init_resource::<TuningFile>()
+            .add_systems(Startup, load_tuning)
+            .add_systems(PreUpdate, apply_tuning)
+            .console_command(
+                "set",
+                "tuning.<key> <value> change a tuning number until the file is saved again",
+                set,
+            );
+    }
+}
+
+/// gameplay numbers from `assets/game.tuning.ron`, live in [`MyAssets::tuning`]
+///
+/// the file is watched on native builds, saving it applies the new values right away
+///
+/// the zone size stays a constant in [`Zone::SIZE`](crate::zones::Zone::SIZE), spatial cells,
+/// saved zones and the map are laid out on it, so it is not a tuning number
+#[derive(Asset, Reflect, Deserialize, Debug, Clone, Copy)]
+#[serde(default)]
+pub struct Tuning {
+    pub ship_speed: f32,
+    pub ship_rotation_speed: f32,
+    pub ship_health: i32,
+    /// asteroid radius is sqrt(bulk) times this
+    pub astroid_radius: f32,
+    /// asteroid hit points per bulk
+    pub astroid_life: i32,
+    /// top drift speed of a new asteroid before the biome multiplier
+    pub astroid_speed: f32,
+    /// loaded zones further than this from the player are unloaded
+    pub zone_despawn_distance: f32,
+    /// projectiles further than this from the player are removed
+    pub max_distance: f32,
+    /// the hook lets go when thrown further than this
+    pub hook_length: f32,
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Self {
+            ship_speed: 25.0,
+            ship_rotation_speed: 2.5,
+            ship_health: 500,
+            astroid_radius: 2.5,
+            astroid_life: 10,
+            astroid_speed: 5.0,
+            zone_despawn_distance: 1500.0,
+            max_distance: 10_000.0,
+            hook_length: 130.0,
+        }
+    }
+}
+
+impl Tuning {
+    /// every number has to be finite and not negative, rng ranges and sizes are built from them
+    ///
+    /// the ones in [`NOT_ZERO`] have to be above zero as well
+    fn check(&self) -> Result<(), String> {
+        for i in 0..self.field_len() {
+            let (Some(name), Some(field)) = (self.name_at(i), self.field_at(i)) else {
+                continue;
+            };
+            let zero_ok = !NOT_ZERO.contains(&name);
+            let bad = match (field.downcast_ref::<f32>(), field.downcast_ref::<i32>()) {
+                (Some(value), _) => {
+                    let ok = value.is_finite() && (*value > 0.0 || zero_ok && *value == 0.0);
+                    (!ok).then(|| value.to_string())
+                }
+                (_, Some(value)) => {
+                    (*value < 0 || !zero_ok && *value == 0).then(|| value.to_string())
+                }
+                _ => None,
+            };
+            if let Some(value) = bad {
+                return Err(format!("tuning.{name} can not be {value}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// kept so the file stays loaded and its changes are seen
+#[derive(Resource, Default)]
+pub(crate) struct TuningFile(pub(crate) Handle<Tuning>);
+
+fn load_tuning(mut file: ResMut<TuningFile>, server: Res<AssetServer>) {
+    file.0 = server.load(TUNING);
+}
+
+fn apply_tuning(
+    mut events: EventReader<AssetEvent<Tuning>>,
+    file: Res<TuningFile>,
+    tunings: Res<Assets<Tuning>>,
+    mut assets: ResMut<MyAssets>,
+) {
+    for event in events.read() {
+        if !event.is_loaded_with_dependencies(&file.0) && !event.is_modified(&file.0) {
+            continue;
+        }
+        let Some(tuning) = tunings.get(&file.0) else {
+            continue;
+        };
+        match tuning.check() {
+            Ok(()) => {
+                info!("tuning applied from {TUNING}");
+                assets.tuning = *tuning;
+            }
+            Err(err) => error!("{TUNING} not applied, {err}"),
+        }
+    }
+}
+
+fn set(world: &mut World, args: &[&str]) -> Result<String, String> {
+    let path: String = arg(args, 0, "setting")?;
+    let Some(key) = path.strip_prefix("tuning.") else {
+        return Err(format!("only tuning.<key> can be set, not '{path}'"));
+    };
+    // changed on a copy so a bad value never reaches the game
+    let mut tuning = world.resource::<MyAssets>().tuning;
+    let keys: Vec<&str> = (0..tuning.field_len())
+        .filter_map(|i| tuning.name_at(i))
+        .collect();
+    let keys = keys.join(", ");
+    let field = tuning
+        .field_mut(key)
+        .ok_or(format!("no tuning.{key}, pick one of {keys}"))?;
+    if let Some(value) = field.downcast_mut::<f32>() {
+        *value = arg(args, 1, "value")?;
+    } else if let Some(value) = field.downcast_mut::<i32>() {
+        *value = arg(args, 1, "value")?;
+    }
+    tuning.check()?;
+    world.resource_mut::<MyAssets>().tuning = tuning;
+    Ok(format!("tuning.{key} = {:?}", tuning.field(key).unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_rejects_zero_health_and_sizes() {
+        assert!(Tuning::default().check().is_ok());
+        let stopped = Tuning {
+            astroid_speed: 0.0,
+            ship_rotation_speed: 0.0,
+            ..default()
+        };
+        assert!(stopped.check().is_ok());
+        for broken in [
+            Tuning {
+                ship_health: 0,
+                ..default()
+            },
+            Tuning {
+                astroid_radius: 0.0,
+                ..default()
+            },
+            Tuning {
+                hook_length: -1.0,
+                ..default()
+            },
+            Tuning {
+                max_distance: f32::NAN,
+                ..default()
+            },
+        ] {
+            assert!(broken.check().is_err());
+        }
+    }
+}
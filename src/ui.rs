@@ -8,13 +8,16 @@ use bevy::{
 
 use sickle_ui::{input_extension::KeyCodeToStringExt, prelude::*, SickleUiPlugin};
 
+use crate::zones::WorldSeed;
+
 pub struct UIPlugin;
 
 impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(SickleUiPlugin);
-        app.add_systems(Startup, setup);
+        app.add_systems(Startup, (setup, spawn_seed_label));
         app.add_systems(Update, synthetic_keyboard);
+        app.add_systems(Update, show_seed.run_if(resource_changed::<WorldSeed>));
     }
 }
 // const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
@@ -26,6 +29,9 @@ const RED_TINT: Srgba = Srgba::new(0.5, 0., 0., 0.1);
 #[derive(Component)]
 struct ForwardBtn;
 
+#[derive(Component)]
+struct SeedLabel;
+
 #[derive(Component, Clone)]
 struct SyntheticKey {
     key_code: KeyCode,
@@ -160,6 +166,31 @@ fn setup(mut commands: Commands) {
         .justify_content(JustifyContent::SpaceBetween);
 }
 
+fn spawn_seed_label(mut commands: Commands) {
+    let style = TextStyle {
+        font_size: 16.0,
+        color: Color::srgba(0.9, 0.9, 0.9, 0.6),
+        ..default()
+    };
+    commands.spawn((
+        TextBundle::from_section("", style)
+            .with_text_justify(JustifyText::Center)
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(4.0),
+                width: Val::Percent(100.0),
+                ..default()
+            }),
+        SeedLabel,
+    ));
+}
+
+fn show_seed(seed: Res<WorldSeed>, mut q: Query<&mut Text, With<SeedLabel>>) {
+    for mut text in q.iter_mut() {
+        text.sections[0].value = format!("seed {}", seed.0);
+    }
+}
+
 fn btn_txt_style() -> TextStyle {
     let txt_style = TextStyle {
         font_size: 40.0,
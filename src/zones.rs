@@ -1,29 +1,66 @@
 use crate::astroids::Rock;
+use crate::biome::Biome;
+use crate::console::{arg, player, ConsoleApp};
+use crate::difficulty::Difficulty;
+use crate::gravity::GravityWell;
+use crate::launch::LaunchConfig;
+use crate::persist::{Persist, Snapshot};
 use crate::schedule::InitStages;
+use crate::sentry::Sentry;
 use crate::ship::Player;
 use crate::ship::SpaceShip;
-use crate::stage::IntoMovingBundle;
+use crate::spatial::SpatialIndex;
+use crate::stage::{IntoMovingBundle, Stage};
+use crate::state::GameState;
+use crate::tuning::Tuning;
+use std::cmp::Reverse;
+use std::collections::VecDeque;
 use std::ops::Add;
 
+use avian2d::prelude::{LinearVelocity, Position};
+use bevy::hierarchy::despawn_with_children_recursive;
+use bevy::math::bounding::{BoundingCircle, IntersectsVolume};
 use bevy::prelude::*;
-use bevy::utils::HashMap;
-use rand::prelude::{Rng, SliceRandom};
+use bevy::tasks::{block_on, poll_once, Task};
+use bevy::utils::{HashMap, HashSet};
+use rand::prelude::Rng;
 use rand_pcg::Pcg64;
 use rand_seeder::Seeder;
 
 use crate::{assets::MyAssets, astroids::Astroid, schedule::InGameSet};
 
+/// tries per asteroid before giving up on fitting it in a zone
+const PLACEMENT_TRIES: usize = 30;
+/// free space kept around each asteroid when a zone is populated
+const PLACEMENT_GAP: f32 = 1.0;
+/// zones from the origin where danger has risen to about two thirds
+const DANGER_DISTANCE: f32 = 15.0;
+/// sentries in a zone at full danger on normal difficulty
+const MAX_SENTRIES: f32 = 3.0;
+
 pub struct ZonePlugin;
 
 impl Plugin for ZonePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<Zones>()
+            .init_resource::<WorldSeed>()
+            .init_resource::<ZoneTasks>()
+            .init_resource::<SpawnBudget>()
+            .register_type::<SpawnBudget>()
             .register_type::<Zones>()
+            .register_type::<WorldSeed>()
             .register_type::<Zone>()
             .register_type::<ZoneState>()
             .register_type::<Population>()
+            .register_type::<Biome>()
             .add_event::<DespawnEvent>()
-            .add_systems(Startup, init_zone.in_set(InitStages::Spawn))
+            .add_systems(Startup, apply_launch_seed.in_set(InitStages::LoadAssets))
+            .add_systems(
+                OnExit(GameState::Loading),
+                init_zone.in_set(InitStages::Spawn),
+            )
+            .add_systems(OnEnter(GameState::GameOver), forget_zones)
+            .add_systems(OnExit(GameState::GameOver), init_zone)
             .add_systems(
                 Update,
                 (despawn_oob_zones, despawn_zone)
@@ -31,65 +68,268 @@ impl Plugin for ZonePlugin {
                     .in_set(InGameSet::Despawn),
             )
             .add_systems(Update, despawn_out_of_zone.in_set(InGameSet::Despawn))
-            .add_systems(Update, spawn_zones.in_set(InGameSet::Spawn));
+            .console_command("teleport", "<row> <col> move the ship to a zone", teleport)
+            .console_command(
+                "seed",
+                "[<seed>] show the seed or start a new universe",
+                seed,
+            )
+            .add_systems(
+                Update,
+                (spawn_zones, collect_zones, drain_zones)
+                    .chain()
+                    .in_set(InGameSet::Spawn),
+            );
     }
 }
 
 #[derive(Component, Debug, Hash, PartialEq, Eq, Reflect, Clone, Copy)]
 pub enum Seed {
     Rock(Astroid),
+    Sentry(Sentry),
 }
+/// what a zone holds while nobody is around
 #[derive(Default, Debug, Reflect)]
 #[reflect(Default)]
-struct Population {
+pub struct Population {
+    /// generated but never spawned
     map: HashMap<Seed, u32>,
+    /// generated but never spawned, always in the middle of the zone
+    well: Option<GravityWell>,
+    /// entities that were spawned once and saved when the zone unloaded
+    #[reflect(ignore)]
+    saved: Snapshot,
 }
 
 impl Population {
-    #[allow(dead_code)]
-    fn size(&self) -> u32 {
-        self.map.iter().map(|(_k, v)| v).sum()
+    pub fn size(&self) -> u32 {
+        let saved = self.saved.components::<Astroid>().count() as u32;
+        self.map.iter().map(|(_k, v)| v).sum::<u32>() + saved
+    }
+
+    pub fn count_kind(&self, kind: Rock) -> u32 {
+        let saved = self
+            .saved
+            .components::<Astroid>()
+            .filter(|astroid| astroid.kind == kind)
+            .count() as u32;
+        self.map
+            .iter()
+            .filter(|(seed, _)| match seed {
+                Seed::Rock(astroid) => astroid.kind == kind,
+                Seed::Sentry(_) => false,
+            })
+            .map(|(_, count)| count)
+            .sum::<u32>()
+            + saved
+    }
+    /// one seed per entity in a fixed order, those placed first are the last to run out of room
+    ///
+    /// sentries go first so crowded zones keep their guards, then the biggest rocks
+    fn seeds(&self) -> Vec<Seed> {
+        let mut seeds: Vec<Seed> = self
+            .map
+            .iter()
+            .flat_map(|(seed, count)| std::iter::repeat_n(*seed, *count as usize))
+            .collect();
+        seeds.sort_by_key(|seed| match seed {
+            Seed::Sentry(sentry) => (0, Reverse(0), sentry.level),
+            Seed::Rock(astroid) => (1, Reverse(astroid.bulk), astroid.kind as u8),
+        });
+        seeds
+    }
+
+    fn insert(&mut self, seed: Seed) {
+        *self.map.entry(seed).or_default() += 1;
     }
-    fn spawn_at(
+
+    /// room taken by the saved entities, fresh seeds are kept out of it
+    fn saved_area(&self, tuning: &Tuning) -> Vec<BoundingCircle> {
+        let at = |transform: Transform, radius| {
+            BoundingCircle::new(transform.translation.truncate(), radius)
+        };
+        let rocks = self
+            .saved
+            .pairs::<Transform, Astroid>()
+            .map(|(transform, astroid)| at(transform, astroid.radius(tuning)));
+        let sentries = self
+            .saved
+            .pairs::<Transform, Sentry>()
+            .map(|(transform, _)| at(transform, Sentry::RADIUS));
+        let wells = self
+            .saved
+            .pairs::<Transform, GravityWell>()
+            .map(|(transform, well)| at(transform, well.radius() * 2.0));
+        rocks.chain(sentries).chain(wells).collect()
+    }
+
+    /// where and how fast each unspawned seed starts, the same for every run of a world seed
+    ///
+    /// rocks within reach of the well of the zone start out in a circular orbit around it
+    fn plan(
         &self,
+        zone: Zone,
+        world: WorldSeed,
+        mut avoid: Vec<BoundingCircle>,
+        tuning: Tuning,
+    ) -> Vec<Pending> {
+        let speed = Biome::at(zone, world).params().speed;
+        let mut rng: Pcg64 = Seeder::from((world, zone, "drift")).make_rng();
+        let well_area = self
+            .well
+            .map(|well| BoundingCircle::new(zone.center(), well.radius() * 2.0));
+        avoid.extend(well_area);
+        zone.place(self.seeds(), world, &avoid, &tuning)
+            .into_iter()
+            .map(|(seed, position)| {
+                let drift = Astroid::random_velocity(&mut rng, &tuning, speed);
+                let offset = position - zone.center();
+                let velocity = match self.well {
+                    Some(well) if offset.length() < well.reach() => well.orbit_velocity(offset),
+                    _ => drift,
+                };
+                Pending {
+                    zone,
+                    seed,
+                    position,
+                    velocity,
+                }
+            })
+            .collect()
+    }
+
+    /// the well is saved with the other entities once spawned, so it is not kept in the population
+    fn spawn_well(&self, cmds: &mut Commands, assets: &MyAssets, zone: Zone) {
+        if let Some(well) = self.well {
+            let transform = Transform::from_translation(zone.center().extend(0.0));
+            cmds.spawn(well.stage(assets, transform));
+        }
+    }
+
+    /// spawn everything right away, saved entities included
+    fn spawn(
+        self,
         cmds: &mut Commands,
         assets: &Res<MyAssets>,
-        mut coords: impl Iterator<Item = Vec2>,
+        zone: Zone,
+        world: WorldSeed,
+        avoid: Option<BoundingCircle>,
     ) {
-        for (seed, count) in &self.map {
-            let coords = coords.by_ref().take(*count as usize);
-            let bundles: Box<[_]> = coords
-                .map(|coord| match seed {
-                    Seed::Rock(astriod) => {
-                        // let velocity = Velocity::default();
-                        let velocity = Astroid::random_velocity();
-                        let transform = Transform::from_translation(coord.extend(0.0));
-                        astriod.bundle(&assets, transform, velocity)
-                    }
-                })
-                .collect();
-            cmds.spawn_batch(bundles);
+        let avoid = avoid
+            .into_iter()
+            .chain(self.saved_area(&assets.tuning))
+            .collect();
+        spawn_pending(cmds, assets, self.plan(zone, world, avoid, assets.tuning));
+        self.spawn_well(cmds, assets, zone);
+        let saved = self.saved;
+        cmds.add(move |world: &mut World| saved.restore(world));
+    }
+}
+
+/// a planned spawn waiting for its turn
+#[derive(Debug, Clone, Copy)]
+struct Pending {
+    zone: Zone,
+    seed: Seed,
+    position: Vec2,
+    velocity: Vec2,
+}
+
+/// rocks go in one batch, the few sentries one by one
+fn spawn_pending(
+    cmds: &mut Commands,
+    assets: &MyAssets,
+    pending: impl IntoIterator<Item = Pending>,
+) {
+    let mut rocks = Vec::new();
+    for pending in pending {
+        let transform = Transform::from_translation(pending.position.extend(0.0));
+        match pending.seed {
+            Seed::Rock(astroid) => rocks.push(astroid.bundle(assets, transform, pending.velocity)),
+            Seed::Sentry(sentry) => {
+                cmds.spawn((sentry.stage(assets, transform), sentry.equipment()));
+            }
         }
     }
+    cmds.spawn_batch(rocks);
+}
+
+/// zones being generated in the background and spawns waiting for the budget
+#[derive(Resource, Default)]
+struct ZoneTasks {
+    generating: HashMap<Zone, Task<Vec<Pending>>>,
+    queue: VecDeque<Pending>,
+}
+
+impl ZoneTasks {
+    fn generate(
+        &mut self,
+        zone: Zone,
+        pop: Population,
+        avoid: Vec<BoundingCircle>,
+        world: WorldSeed,
+        tuning: Tuning,
+    ) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let task = bevy::tasks::AsyncComputeTaskPool::get()
+                .spawn(async move { pop.plan(zone, world, avoid, tuning) });
+            self.generating.insert(zone, task);
+        }
+        // no worker threads on the web, plan right away but still spawn over several frames
+        #[cfg(target_arch = "wasm32")]
+        self.queue.extend(pop.plan(zone, world, avoid, tuning));
+    }
+
+    /// drop work for a zone that is unloading, what never made it into the world is handed back
+    fn cancel(&mut self, zone: Zone) -> Vec<Seed> {
+        let mut pending = self
+            .generating
+            .remove(&zone)
+            .map(block_on)
+            .unwrap_or_default();
+        let (gone, keep): (VecDeque<_>, VecDeque<_>) =
+            self.queue.drain(..).partition(|p| p.zone == zone);
+        self.queue = keep;
+        pending.extend(gone);
+        pending.into_iter().map(|p| p.seed).collect()
+    }
+}
+
+/// how many queued zone entities are spawned each frame
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct SpawnBudget(pub usize);
+
+impl Default for SpawnBudget {
+    fn default() -> Self {
+        Self(50)
+    }
+}
 
-    fn spawn(&self, cmds: &mut Commands, assets: &Res<MyAssets>, zone: Zone) {
-        let coords = zone.rand_coordinates();
-        self.spawn_at(cmds, assets, coords);
+impl Seed {
+    fn radius(&self, tuning: &Tuning) -> f32 {
+        match self {
+            Seed::Rock(astroid) => astroid.radius(tuning),
+            Seed::Sentry(_) => Sentry::RADIUS,
+        }
     }
 }
 
-impl From<Zone> for Population {
-    fn from(zone: Zone) -> Self {
-        let mut rng: Pcg64 = Seeder::from(zone).make_rng();
-        let n: u8 = rng.gen_range(10..100);
-        let size_dist = rand_distr::Binomial::new(15, 0.1).unwrap();
-        let kind_dist = rand_distr::Standard;
-        let mut kind_gen = rng.clone().sample_iter(kind_dist);
+impl Population {
+    /// what a zone holds before anyone has been there, more and worse further from the origin
+    pub fn generate(zone: Zone, world: WorldSeed, difficulty: Difficulty) -> Self {
+        let danger = zone.danger();
+        let params = Biome::at(zone, world).params().scaled(danger);
+        let mut rng: Pcg64 = Seeder::from((world, zone)).make_rng();
+        let n: u8 = rng.gen_range(params.count.0..params.count.1);
+        let size_dist = rand_distr::Binomial::new(params.bulk.0, params.bulk.1).unwrap();
+        let mut kind_rng = rng.clone();
         let astriods = rng.sample_iter(size_dist).map(|rand| {
-            let bulk = ((rand + 1).pow(2)) as u8;
+            let bulk = ((rand + 1).pow(2)).min(u8::MAX as u64) as u8;
             Astroid {
                 bulk,
-                kind: kind_gen.next().unwrap(),
+                kind: params.rock(&mut kind_rng),
             }
         });
         let mut map: HashMap<Seed, _> = HashMap::new();
@@ -103,14 +343,26 @@ impl From<Zone> for Population {
                 }
             }
         }
-        Population { map }
+        let well = zone.well(world);
+        let mut sentry_rng: Pcg64 = Seeder::from((world, zone, "sentries")).make_rng();
+        let expected = danger * MAX_SENTRIES * difficulty.params().sentries;
+        let sentries = expected as u32 + u32::from(sentry_rng.gen::<f32>() < expected.fract());
+        if sentries > 0 {
+            let level = (danger * Sentry::MAX_LEVEL as f32).round() as u8;
+            map.insert(Seed::Sentry(Sentry::new(level)), sentries);
+        }
+        Population {
+            map,
+            well,
+            ..default()
+        }
     }
 }
 
 #[derive(Component, Reflect, Copy, Clone, Debug, Default, Hash, PartialEq, Eq)]
-struct Zone {
-    row: i32,
-    col: i32,
+pub struct Zone {
+    pub row: i32,
+    pub col: i32,
 }
 
 impl From<Vec2> for Zone {
@@ -143,13 +395,27 @@ impl Add<&Zone> for Zone {
 // #[allow(dead_code)]
 impl Zone {
     /// halfsize of square
-    const SIZE: f32 = 300.0;
-    #[allow(dead_code)]
-    fn new(row: i32, col: i32) -> Self {
+    pub const SIZE: f32 = 300.0;
+    pub fn new(row: i32, col: i32) -> Self {
         Self { row, col }
     }
 
-    fn center(&self) -> Vec2 {
+    /// 0 at the origin rising towards 1 far out, how hostile the zone is
+    pub fn danger(&self) -> f32 {
+        let distance = Vec2::new(self.col as f32, self.row as f32).length();
+        1.0 - (-distance / DANGER_DISTANCE).exp()
+    }
+
+    /// planet or black hole in the middle of the zone, the origin is kept calm
+    pub fn well(&self, world: WorldSeed) -> Option<GravityWell> {
+        if *self == Zone::default() {
+            return None;
+        }
+        let mut rng: Pcg64 = Seeder::from((world, *self, "well")).make_rng();
+        Biome::at(*self, world).params().well(&mut rng)
+    }
+
+    pub fn center(&self) -> Vec2 {
         let x = self.col as f32 * Self::SIZE * 2.;
         let y = self.row as f32 * Self::SIZE * 2.;
         Vec2 { x, y }
@@ -160,19 +426,6 @@ impl Zone {
         self.center().distance(rhs)
     }
 
-    fn min_x(&self) -> f32 {
-        self.center()[0] - Self::SIZE
-    }
-    fn max_x(&self) -> f32 {
-        self.center()[0] + Self::SIZE
-    }
-    fn min_y(&self) -> f32 {
-        self.center()[1] - Self::SIZE
-    }
-    fn max_y(&self) -> f32 {
-        self.center()[1] + Self::SIZE
-    }
-
     const ADJECENT: [[i32; 2]; 9] = [
         [-1, -1],
         [-1, 0],
@@ -188,98 +441,162 @@ impl Zone {
         Self::ADJECENT.map(|rc| Into::<Self>::into(rc) + self)
     }
 
-    /// TODO change signature
-    pub fn grid_coordinates(&self) -> Vec<Vec2> {
-        let n = 15;
-
-        let cell_size = Self::SIZE * 2.0 / n as f32;
-        let origo = self.center() - Self::SIZE + cell_size / 2.0;
-        let coords: Vec<_> = (0..n)
-            .flat_map(|r| {
-                (0..n).map(move |c| {
-                    let x = origo.x + cell_size * c as f32;
-                    let y = origo.y + cell_size * r as f32;
-                    Vec2 { x, y }
+    /// dart throwing inside the zone, deterministic per zone
+    ///
+    /// seeds are kept clear of each other, the zone edges and the avoided areas,
+    /// a seed that finds no room is left out
+    pub fn place(
+        &self,
+        seeds: Vec<Seed>,
+        world: WorldSeed,
+        avoid: &[BoundingCircle],
+        tuning: &Tuning,
+    ) -> Vec<(Seed, Vec2)> {
+        let mut rng: Pcg64 = Seeder::from((world, *self, "placement")).make_rng();
+        let mut placed: Vec<(Seed, BoundingCircle)> = Vec::with_capacity(seeds.len());
+        for seed in seeds {
+            let radius = seed.radius(tuning) + PLACEMENT_GAP;
+            let reach = Self::SIZE - radius;
+            if reach <= 0.0 {
+                continue;
+            }
+            let spot = (0..PLACEMENT_TRIES)
+                .map(|_| {
+                    let x = rng.gen_range(-reach..reach);
+                    let y = rng.gen_range(-reach..reach);
+                    BoundingCircle::new(self.center() + Vec2::new(x, y), radius)
                 })
-            })
-            .collect();
-        // coords
-        coords
+                .find(|circle| {
+                    avoid.iter().all(|area| !circle.intersects(area))
+                        && placed.iter().all(|(_, other)| !circle.intersects(other))
+                });
+            match spot {
+                Some(circle) => placed.push((seed, circle)),
+                None => debug!("no room for {:?} in {:?}", seed, self),
+            }
+        }
+        placed
+            .into_iter()
+            .map(|(seed, circle)| (seed, circle.center))
+            .collect()
     }
+}
 
-    pub fn rand_coordinates(&self) -> impl Iterator<Item = Vec2> {
-        let mut rng: Pcg64 = Seeder::from(self).make_rng();
-        // let x_range = rand::distributions::Uniform::new(self.min_x(), self.max_x());
-        // let x_iter = rng.sample_iter(x_range);
+/// mixed into every zone rng, the same seed always gives the same universe
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[reflect(Resource)]
+pub struct WorldSeed(pub u64);
 
-        // let offset = Zone {
-        //     row: 1337,
-        //     col: 7331,
-        // };
-        // let zone2 = *self + &offset;
-        // let rng: Pcg64 = Seeder::from(zone2).make_rng();
-        // let y_range = rand::distributions::Uniform::new(self.min_y(), self.max_y());
-        // let y_iter = rng.sample_iter(y_range);
+impl Default for WorldSeed {
+    /// a fresh universe every launch
+    fn default() -> Self {
+        Self(rand::random())
+    }
+}
 
-        // x_iter.zip(y_iter).map(|(x, y)| Vec2 { x, y })
-        let mut coords = self.grid_coordinates();
-        coords.shuffle(&mut rng);
-        coords.into_iter().cycle()
+fn apply_launch_seed(launch: Res<LaunchConfig>, mut world: ResMut<WorldSeed>) {
+    if let Some(seed) = launch.seed {
+        *world = WorldSeed(seed);
     }
+}
 
-    fn inside(&self, v: Vec2) -> bool {
-        if v.x < self.min_x() {
-            return false;
-        }
-        if v.x >= self.max_x() {
-            return false;
-        }
-        if v.y < self.min_y() {
-            return false;
-        }
-        if v.y >= self.max_y() {
-            return false;
-        }
-        true
+fn teleport(world: &mut World, args: &[&str]) -> Result<String, String> {
+    let zone = Zone::new(arg(args, 0, "row")?, arg(args, 1, "col")?);
+    let ship = player(world)?;
+    let center = zone.center();
+    let mut ship = world.entity_mut(ship);
+    if let Some(mut transform) = ship.get_mut::<Transform>() {
+        transform.translation = center.extend(transform.translation.z);
+    }
+    if let Some(mut position) = ship.get_mut::<Position>() {
+        position.0 = center;
     }
+    if let Some(mut velocity) = ship.get_mut::<LinearVelocity>() {
+        velocity.0 = Vec2::ZERO;
+    }
+    Ok(format!("teleported to {zone:?}"))
+}
+
+fn seed(world: &mut World, args: &[&str]) -> Result<String, String> {
+    if args.is_empty() {
+        return Ok(format!("seed {}", world.resource::<WorldSeed>().0));
+    }
+    let seed = WorldSeed(arg(args, 0, "seed")?);
+    world.insert_resource(seed);
+    world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::GameOver);
+    Ok(format!("starting over with seed {}", seed.0))
 }
 
 #[derive(Resource, Reflect, Default)]
 #[reflect(Resource)]
-struct Zones {
+pub struct Zones {
     // active: SpawnZone,
-    state: HashMap<Zone, ZoneState>,
-}
-impl Zones {
-    fn insert(&mut self, zone: Zone, seed: Seed) {
-        match self.state.get_mut(&zone) {
-            Some(pop) => pop.insert(seed),
-            None => {
-                let pop: Population = zone.into();
-                let mut depop: ZoneState = pop.into();
-                depop.insert(seed);
-                self.state.insert_unique_unchecked(zone, depop);
-            }
+    pub state: HashMap<Zone, ZoneState>,
+    /// zones that have been loaded around the player, strays can put others in `state`
+    #[reflect(ignore)]
+    pub visited: HashSet<Zone>,
+}
+/// save entities into the population of their zone and remove them from the world
+fn stash(world: &mut World, zone: Zone, mut entities: Vec<Entity>) {
+    // another stash this frame may have taken some already
+    entities.retain(|entity| world.get_entity(*entity).is_some());
+    let snapshot = Snapshot::take(world, &entities);
+    for entity in entities {
+        despawn_with_children_recursive(world, entity);
+    }
+    let seed = *world.resource::<WorldSeed>();
+    let difficulty = *world.resource::<Difficulty>();
+    let mut zones = world.resource_mut::<Zones>();
+    let state = zones
+        .state
+        .entry(zone)
+        .or_insert_with(|| Population::generate(zone, seed, difficulty).into());
+    match state {
+        ZoneState::Despawned(pop) => pop.saved.append(snapshot),
+        ZoneState::Spawned => {
+            *state = ZoneState::Despawned(Population {
+                saved: snapshot,
+                ..default()
+            })
         }
     }
 }
 
-fn init_zone(mut cmds: Commands, mut zones: ResMut<Zones>, assets: Res<MyAssets>) {
-    let zone: Zone = [0, 0].into();
-    let pop: Population = zone.into();
-    let coords = zone
-        .rand_coordinates()
-        .filter(|coord| coord.distance(Vec2::ZERO) > 30.0);
-    pop.spawn_at(&mut cmds, &assets, coords);
+fn init_zone(
+    mut cmds: Commands,
+    mut zones: ResMut<Zones>,
+    assets: Res<MyAssets>,
+    world: Res<WorldSeed>,
+    launch: Res<LaunchConfig>,
+    difficulty: Res<Difficulty>,
+) {
+    let zone = launch.zone.unwrap_or_default();
+    let pop = Population::generate(zone, *world, *difficulty);
+    // keep the player start clear
+    let start = BoundingCircle::new(launch.start(*world), 30.0);
+    pop.spawn(&mut cmds, &assets, zone, *world, Some(start));
     zones
         .state
         .insert_unique_unchecked(zone, ZoneState::Spawned);
+    zones.visited.insert(zone);
 }
+/// the old universe is gone, zones are generated again on restart
+fn forget_zones(mut zones: ResMut<Zones>, mut tasks: ResMut<ZoneTasks>) {
+    zones.state.clear();
+    zones.visited.clear();
+    *tasks = ZoneTasks::default();
+}
+
 fn spawn_zones(
     mut cmds: Commands,
     q: Query<&Transform, With<SpaceShip>>,
     mut zones: ResMut<Zones>,
+    mut tasks: ResMut<ZoneTasks>,
+    world: Res<WorldSeed>,
     assets: Res<MyAssets>,
+    difficulty: Res<Difficulty>,
 ) {
     let Ok(player) = q.get_single() else {
         return;
@@ -287,25 +604,46 @@ fn spawn_zones(
 
     let zone: Zone = player.translation.truncate().into();
     for zone in zone.neighbors() {
-        match zones.state.get(&zone) {
-            None => {
-                let pop: Population = zone.into();
-                pop.spawn(&mut cmds, &assets, zone);
-                zones
-                    .state
-                    .insert_unique_unchecked(zone, ZoneState::Spawned);
-            }
-            Some(depop) => {
-                if let ZoneState::Despawned(pop) = depop {
-                    pop.spawn(&mut cmds, &assets, zone);
-                    zones.state.insert(zone, ZoneState::Spawned);
-                }
-            }
-        }
+        let pop = match zones.state.insert(zone, ZoneState::Spawned) {
+            None => Population::generate(zone, *world, *difficulty),
+            Some(ZoneState::Spawned) => continue,
+            Some(ZoneState::Despawned(pop)) => pop,
+        };
+        zones.visited.insert(zone);
+        pop.spawn_well(&mut cmds, &assets, zone);
+        // the well stays in the population so rocks are planned around it
+        let avoid = pop.saved_area(&assets.tuning);
+        let Population { map, well, saved } = pop;
+        cmds.add(move |world: &mut World| saved.restore(world));
+        let unspawned = Population {
+            map,
+            well,
+            ..default()
+        };
+        tasks.generate(zone, unspawned, avoid, *world, assets.tuning);
     }
 }
 
-const DESPAWN_DIST: f32 = Zone::SIZE * 5.;
+fn collect_zones(mut tasks: ResMut<ZoneTasks>) {
+    let ZoneTasks { generating, queue } = &mut *tasks;
+    generating.retain(|_, task| match block_on(poll_once(task)) {
+        Some(pending) => {
+            queue.extend(pending);
+            false
+        }
+        None => true,
+    });
+}
+
+fn drain_zones(
+    mut cmds: Commands,
+    mut tasks: ResMut<ZoneTasks>,
+    budget: Res<SpawnBudget>,
+    assets: Res<MyAssets>,
+) {
+    let n = budget.0.min(tasks.queue.len());
+    spawn_pending(&mut cmds, &assets, tasks.queue.drain(..n));
+}
 
 #[derive(Event)]
 struct DespawnEvent {
@@ -316,10 +654,12 @@ fn despawn_oob_zones(
     q: Query<&Transform, With<Player>>,
     mut writer: EventWriter<DespawnEvent>,
     zones: Res<Zones>,
+    assets: Res<MyAssets>,
 ) {
     let Ok(player) = q.get_single() else {
         return;
     };
+    let dist = assets.tuning.zone_despawn_distance;
     for zone in zones
         .state
         .iter()
@@ -328,7 +668,7 @@ fn despawn_oob_zones(
             ZoneState::Despawned(_) => false,
         })
         .filter_map(|(zone, _)| {
-            (zone.center().distance(player.translation.truncate()) > DESPAWN_DIST).then(|| zone)
+            (zone.center().distance(player.translation.truncate()) > dist).then(|| zone)
         })
     {
         writer.send(DespawnEvent { zone: *zone });
@@ -337,48 +677,58 @@ fn despawn_oob_zones(
 
 fn despawn_out_of_zone(
     mut cmds: Commands,
-    q: Query<(Entity, &Transform, &Astroid)>,
+    q: Query<(), With<Persist>>,
     player_q: Query<&Transform, With<Player>>,
-    mut zones: ResMut<Zones>,
+    index: Res<SpatialIndex>,
+    assets: Res<MyAssets>,
 ) {
-    let dist = Zone::SIZE * 8.;
+    // past the corner of the furthest zone still loaded, so strays never go back into a live zone
+    let dist = assets.tuning.zone_despawn_distance + Zone::SIZE * std::f32::consts::SQRT_2;
     let Ok(player) = player_q.get_single() else {
         return;
     };
-    for (ent, trans, &astroid) in q.iter() {
-        let distance = trans.translation.distance(player.translation);
-        if distance > dist {
-            let zone: Zone = trans.translation.truncate().into();
-            cmds.entity(ent).despawn_recursive();
-            zones.insert(zone, Seed::Rock(astroid));
+    let mut strays: HashMap<Zone, Vec<Entity>> = HashMap::new();
+    for (ent, position) in index.outside(player.translation.truncate(), dist) {
+        if q.contains(ent) {
+            strays.entry(position.into()).or_default().push(ent);
         }
     }
+    for (zone, entities) in strays {
+        cmds.add(move |world: &mut World| stash(world, zone, entities));
+    }
 }
 fn despawn_zone(
     mut cmds: Commands,
-    q: Query<(Entity, &Transform, &Astroid)>,
+    q: Query<(), With<Persist>>,
     mut reader: EventReader<DespawnEvent>,
     mut zones: ResMut<Zones>,
+    mut tasks: ResMut<ZoneTasks>,
+    index: Res<SpatialIndex>,
 ) {
     for event in reader.read() {
-        q.iter().for_each(|(ent, transform, astriod)| {
-            if event.zone.inside(transform.translation.truncate()) {
-                let pop = zones.state.get_mut(&event.zone).unwrap();
-                pop.insert(Seed::Rock(*astriod));
-                cmds.entity(ent).despawn_recursive();
-            }
-        });
+        let zone = event.zone;
+        let entities: Vec<Entity> = index
+            .in_zone(zone)
+            .map(|(ent, _)| ent)
+            .filter(|ent| q.contains(*ent))
+            .collect();
+        cmds.add(move |world: &mut World| stash(world, zone, entities));
         match zones.state.get_mut(&event.zone).unwrap() {
             ZoneState::Despawned(_) => (),
             pop => {
                 *pop = ZoneState::Despawned(Population::default());
             }
         }
+        if let Some(ZoneState::Despawned(pop)) = zones.state.get_mut(&event.zone) {
+            for seed in tasks.cancel(event.zone) {
+                pop.insert(seed);
+            }
+        }
     }
 }
 
 #[derive(Debug, Default, Reflect)]
-enum ZoneState {
+pub enum ZoneState {
     #[default]
     Spawned,
     Despawned(Population),
@@ -390,20 +740,74 @@ impl From<Population> for ZoneState {
     }
 }
 
-impl ZoneState {
-    fn insert(&mut self, seed: Seed) {
-        match self {
-            ZoneState::Spawned => {
-                let mut map = HashMap::new();
-                map.insert_unique_unchecked(seed, 1);
-                *self = ZoneState::Despawned(Population { map })
-            }
-            ZoneState::Despawned(pop) => match pop.map.get_mut(&seed) {
-                Some(count) => *count += 1,
-                None => {
-                    pop.map.insert_unique_unchecked(seed, 1);
-                }
-            },
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn danger_is_zero_at_the_origin() {
+        assert_eq!(Zone::default().danger(), 0.0);
+    }
+
+    #[test]
+    fn danger_rises_towards_one() {
+        let dangers: Vec<f32> = [1, 5, 15, 50, 500]
+            .into_iter()
+            .map(|col| Zone::new(0, col).danger())
+            .collect();
+        assert!(dangers.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(dangers.iter().all(|danger| (0.0..=1.0).contains(danger)));
+        assert!((dangers[2] - (1.0 - (-1.0f32).exp())).abs() < 1e-6);
+        assert_eq!(Zone::new(3, 4).danger(), Zone::new(-4, 3).danger());
+    }
+
+    #[test]
+    fn sentries_are_placed_first() {
+        let mut pop = Population::default();
+        pop.insert(Seed::Rock(Astroid {
+            bulk: 200,
+            kind: Rock::Metal,
+        }));
+        pop.insert(Seed::Sentry(Sentry::new(2)));
+        assert!(matches!(pop.seeds()[0], Seed::Sentry(_)));
+    }
+
+    #[test]
+    fn placement_is_spread_out_and_repeatable() {
+        let tuning = Tuning::default();
+        let world = WorldSeed(7);
+        let zone = Zone::new(2, -3);
+        let rock = |bulk| {
+            Seed::Rock(Astroid {
+                bulk,
+                kind: Rock::Stone,
+            })
+        };
+        let seeds: Vec<Seed> = (0..40)
+            .map(|i| rock(1 + i * 6))
+            .chain(std::iter::repeat_n(Seed::Sentry(Sentry::new(1)), 5))
+            .collect();
+        let avoid = [BoundingCircle::new(zone.center() + Vec2::splat(50.0), 80.0)];
+
+        let placed = zone.place(seeds.clone(), world, &avoid, &tuning);
+        assert!(!placed.is_empty());
+        assert_eq!(placed, zone.place(seeds.clone(), world, &avoid, &tuning));
+        assert_ne!(
+            placed,
+            Zone::new(2, -2).place(seeds, world, &avoid, &tuning)
+        );
+
+        let circles: Vec<BoundingCircle> = placed
+            .iter()
+            .map(|(seed, position)| BoundingCircle::new(*position, seed.radius(&tuning)))
+            .collect();
+        for (i, circle) in circles.iter().enumerate() {
+            let offset = (circle.center - zone.center()).abs();
+            assert!(offset.max_element() + circle.radius() <= Zone::SIZE);
+            assert!(!circle.intersects(&avoid[0]));
+            assert!(circles[i + 1..]
+                .iter()
+                .all(|other| !circle.intersects(other)));
         }
     }
 }